@@ -0,0 +1,391 @@
+use crate::{
+    err,
+    geometry::{Axis, Blade, Blank, ConstOne, Mvect, One, SquareMap, Squared, Zero},
+    traits::{RecipOrZero, Squareroot},
+};
+
+impl Zero for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+}
+impl One for f64 {
+    fn one() -> Self {
+        1.0
+    }
+}
+impl Squareroot for f64 {
+    type Output = Self;
+    fn sqrt(self) -> Self::Output {
+        f64::sqrt(self)
+    }
+}
+impl RecipOrZero for f64 {
+    fn recip_or_zero(self) -> Self {
+        if self == 0.0 { 0.0 } else { self.recip() }
+    }
+}
+
+/// a metric built at runtime, the numeric counterpart of the `square!` declarations
+///     let pga2d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1)])?;
+///     let point = pga2d.mv([("e1", 1.0)])?.wedge(pga2d.mv([("e2", 1.0)])?);
+#[derive(Debug, Clone)]
+pub struct Algebra(SquareMap);
+
+impl Algebra {
+    pub fn new<'s>(squares: impl IntoIterator<Item = (&'s str, i8)>) -> syn::Result<Self> {
+        let mut map = SquareMap::default();
+        for (name, square) in squares {
+            let axis: Axis = name.parse()?;
+            let square = match square {
+                -1 => -Squared::One,
+                0 => Squared::Zero,
+                1 => Squared::One,
+                n => return Err(err!(format!("{n} is not a valid basis square"))),
+            };
+            if !map.insert(axis, square) {
+                return Err(err!(format!("duplicate squares defined for {name}")));
+            }
+        }
+        map.hone_ps();
+        Ok(Self(map))
+    }
+    pub fn squares(&self) -> &SquareMap {
+        &self.0
+    }
+    /// the psuedoscalar, for `dual` and friends
+    pub fn ps(&self) -> Blade {
+        self.0.1.clone()
+    }
+    /// the canonical blade for a name, e.g. `e21` is `-e12`
+    pub fn blade(&self, name: &str) -> syn::Result<Blade> {
+        Ok(name.parse::<Blank>()?.hone(&self.0))
+    }
+    /// a multivector from `(blade, value)` pairs, repeated blades are summed
+    pub fn mv<'s>(
+        &self,
+        values: impl IntoIterator<Item = (&'s str, f64)>,
+    ) -> syn::Result<Mvect<'_, f64>> {
+        values
+            .into_iter()
+            .try_fold(Mvect::empty(&self.0), |mv, (name, value)| {
+                Ok(mv.add_blade_value(self.blade(name)?, value))
+            })
+    }
+}
+
+impl From<SquareMap> for Algebra {
+    /// squares already honed by the `square!` declarations of a module
+    fn from(squares: SquareMap) -> Self {
+        Self(squares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(alg: &Algebra, mv: &Mvect<'_, f64>, name: &str) -> f64 {
+        mv.get(&alg.blade(name).unwrap()).unwrap_or(0.0)
+    }
+
+    #[test]
+    fn test_pga2d_meet() -> syn::Result<()> {
+        let pga2d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1)])?;
+        // the lines x = 1 and y = 2
+        let a = pga2d.mv([("e1", 1.0), ("e0", -1.0)])?;
+        let b = pga2d.mv([("e2", 1.0), ("e0", -2.0)])?;
+        let p = a.wedge(b);
+        assert_eq!(get(&pga2d, &p, "e12"), 1.0);
+        assert_eq!(get(&pga2d, &p, "e01"), 2.0);
+        assert_eq!(get(&pga2d, &p, "e20"), 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotor_sandwich() -> syn::Result<()> {
+        let vga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let angle: f64 = 0.3;
+        let (s, c) = (angle / 2.0).sin_cos();
+        let rotor = vga3d.mv([("scalar", c), ("e12", -s)])?;
+        let v = rotor.sandwich(vga3d.mv([("e1", 1.0)])?);
+        assert!((get(&vga3d, &v, "e1") - angle.cos()).abs() < 1e-12);
+        assert!((get(&vga3d, &v, "e2") - angle.sin()).abs() < 1e-12);
+        assert!(get(&vga3d, &v, "e3").abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_norm_and_dual() -> syn::Result<()> {
+        let vga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let v = vga3d.mv([("e1", 3.0), ("e2", 4.0)])?;
+        assert_eq!(get(&vga3d, &v.clone().norm(), "scalar"), 5.0);
+        let dual = v.dual(vga3d.ps());
+        assert_eq!(dual.clone().grade(2).into_iter().count(), 2);
+        assert_eq!(dual.into_iter().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bulk_and_weight() -> syn::Result<()> {
+        let pga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1), ("e4", 0)])?;
+        let p = pga3d.mv([("e1", 6.0), ("e2", 8.0), ("e4", 2.0)])?;
+        assert_eq!(p.clone().bulk().into_iter().count(), 2);
+        assert_eq!(get(&pga3d, &p.clone().weight(), "e4"), 2.0);
+        assert_eq!(get(&pga3d, &p.clone().bulk_norm(), "scalar"), 10.0);
+        assert_eq!(get(&pga3d, &p.clone().weight_norm(), "scalar"), 2.0);
+        let unit = p.unitize();
+        assert_eq!(get(&pga3d, &unit, "e1"), 3.0);
+        assert_eq!(get(&pga3d, &unit, "e4"), 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_reject_reflect() -> syn::Result<()> {
+        let vga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let v = vga3d.mv([("e1", 1.0), ("e2", 2.0), ("e3", 3.0)])?;
+        let x = vga3d.mv([("e1", 2.0)])?;
+        let xy = vga3d.mv([("e12", 1.0)])?;
+        let onto = v.clone().project(x.clone());
+        assert_eq!(get(&vga3d, &onto, "e1"), 1.0);
+        assert_eq!(get(&vga3d, &onto, "e2"), 0.0);
+        let from = v.clone().reject(x.clone());
+        assert_eq!(get(&vga3d, &from, "e1"), 0.0);
+        assert_eq!(get(&vga3d, &from, "e3"), 3.0);
+        let plane = v.clone().project(xy.clone());
+        assert_eq!(get(&vga3d, &plane, "e2"), 2.0);
+        assert_eq!(get(&vga3d, &plane, "e3"), 0.0);
+        // a vector is the normal of the hyperplane it reflects in
+        let mirrored = v.clone().reflect(x);
+        assert_eq!(get(&vga3d, &mirrored, "e1"), -1.0);
+        assert_eq!(get(&vga3d, &mirrored, "e2"), 2.0);
+        // a bivector reflects in what it represents in PGA, the e3 axis
+        let mirrored = v.reflect(xy);
+        assert_eq!(get(&vga3d, &mirrored, "e2"), -2.0);
+        assert_eq!(get(&vga3d, &mirrored, "e3"), 3.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_null() -> syn::Result<()> {
+        // the ideal line of PGA squares to zero, so its inverse divides by zero
+        let pga2d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1)])?;
+        let ideal = pga2d.mv([("e0", 1.0)])?;
+        assert!(get(&pga2d, &ideal.inv(), "e0").is_infinite());
+        // and so does a lightlike vector, which doesn't vanish in products
+        let sta = Algebra::new([("e1", 1), ("e2", -1)])?;
+        let light = sta.mv([("e1", 1.0), ("e2", 1.0)])?;
+        let x = sta.mv([("e1", 1.0)])?;
+        let onto = x.clone().project(light.clone());
+        assert!(get(&sta, &onto, "e1").is_infinite());
+        let mirrored = x.reflect(light);
+        assert!(mirrored.into_iter().any(|(_, v)| !v.is_finite()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_meet_join() -> syn::Result<()> {
+        let pga3d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let x = pga3d.mv([("e1", 1.0)])?;
+        let y = pga3d.mv([("e2", 1.0)])?;
+        // disjoint blades join into their wedge, even with a null axis
+        let xy = x.clone().join(y.clone());
+        assert_eq!(get(&pga3d, &xy, "e12"), 1.0);
+        assert_eq!(get(&pga3d, &x.clone().meet(y.clone()), "scalar"), 1.0);
+        // two lines sharing the x plane, where `regressive` gives zero
+        let other = x.clone().wedge(pga3d.mv([("e0", 1.0), ("e3", 1.0)])?);
+        let shared = xy.clone().meet(other.clone());
+        assert_eq!(get(&pga3d, &shared, "e2"), 0.0);
+        assert_eq!(get(&pga3d, &shared, "e0"), 0.0);
+        assert_ne!(get(&pga3d, &shared, "e1"), 0.0);
+        assert!(
+            xy.clone()
+                .regressive(other.clone())
+                .into_iter()
+                .all(|(_, v)| v == 0.0)
+        );
+        let span = xy.join(other);
+        let e012 = get(&pga3d, &span, "e012");
+        assert_ne!(e012, 0.0);
+        assert_eq!(get(&pga3d, &span, "e123"), e012);
+        Ok(())
+    }
+
+    #[test]
+    fn test_factorize() -> syn::Result<()> {
+        let pga3d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let p = pga3d.mv([("e0", 3.0), ("e1", 1.0)])?;
+        let q = pga3d.mv([("e2", -2.0), ("e3", 0.5)])?;
+        let line = p.wedge(q);
+        let factors = line.clone().factorize();
+        assert_eq!(factors.len(), 2);
+        let [x, y] = [factors[0].clone(), factors[1].clone()];
+        let wedge = x.wedge(y);
+        for blade in ["e01", "e12", "e23", "e02", "e03", "e31"] {
+            let (lhs, rhs) = (get(&pga3d, &wedge, blade), get(&pga3d, &line, blade));
+            assert!((lhs - rhs).abs() < 1e-12, "{blade}: {lhs} != {rhs}");
+        }
+        assert!(pga3d.mv([("e0", 0.0)])?.factorize().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_outermorphism() -> syn::Result<()> {
+        let vga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let scale = vec![
+            vec![2.0, 0.0, 0.0],
+            vec![0.0, 3.0, 0.0],
+            vec![0.0, 0.0, 4.0],
+        ];
+        let mv = vga3d.mv([("scalar", 5.0), ("e1", 1.0), ("e23", 1.0), ("e123", 1.0)])?;
+        let scaled = mv.outermorphism(&scale);
+        assert_eq!(get(&vga3d, &scaled, "scalar"), 5.0);
+        assert_eq!(get(&vga3d, &scaled, "e1"), 2.0);
+        assert_eq!(get(&vga3d, &scaled, "e23"), 12.0);
+        assert_eq!(get(&vga3d, &scaled, "e123"), 24.0);
+        // x += y shears e2 into e1 + e2, e12 is unchanged
+        let shear = vec![
+            vec![1.0, 1.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let e2 = vga3d.mv([("e2", 1.0)])?.outermorphism(&shear);
+        assert_eq!(get(&vga3d, &e2, "e1"), 1.0);
+        let e12 = vga3d.mv([("e12", 1.0)])?.outermorphism(&shear);
+        assert_eq!(get(&vga3d, &e12, "e12"), 1.0);
+        assert_eq!(get(&vga3d, &e12, "e13"), 0.0);
+        assert_eq!(get(&vga3d, &e12, "e23"), 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_matrix_roundtrip() -> syn::Result<()> {
+        let (c, s) = (0.3f64.cos(), 0.3f64.sin());
+        let vga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let rotor = vga3d.mv([("scalar", c), ("e12", -s)])?;
+        let m = rotor.clone().to_matrix();
+        // rotates e1 towards e2 by twice the half angle
+        let angle = 0.6f64;
+        assert!((m[0][0] - angle.cos()).abs() < 1e-12);
+        assert!((m[1][0] - angle.sin()).abs() < 1e-12);
+        assert!((m[2][2] - 1.0).abs() < 1e-12);
+        let near = |a: &Mvect<'_, f64>, b: &Mvect<'_, f64>, alg: &Algebra| {
+            alg.squares()
+                .basis()
+                .into_iter()
+                .map(Blade::from)
+                .all(|blade| {
+                    let d = a.get(&blade).unwrap_or(0.0) - b.get(&blade).unwrap_or(0.0);
+                    d.abs() < 1e-12
+                })
+        };
+        assert!(near(
+            &Mvect::from_matrix(&m, vga3d.squares()),
+            &rotor,
+            &vga3d
+        ));
+        // a screw along z, the pseudoscalar part has to be divided out
+        let pga3d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let rotor = pga3d.mv([("scalar", c), ("e12", -s)])?;
+        let translator = pga3d.mv([("scalar", 1.0), ("e01", -0.5), ("e03", -1.5)])?;
+        let motor = translator.mul(rotor);
+        let m = motor.clone().to_matrix();
+        assert!(near(
+            &Mvect::from_matrix(&m, pga3d.squares()),
+            &motor,
+            &pga3d
+        ));
+        Ok(())
+    }
+
+    /// the parts square to scalars, commute and sum back to the bivector
+    fn check_split(b: Mvect<'_, f64>) {
+        let is_zero = |mv: Mvect<'_, f64>| mv.into_iter().all(|(_, v)| v.abs() < 1e-12);
+        let [b1, b2] = <[_; 2]>::try_from(b.clone().split()).unwrap();
+        for part in [&b1, &b2] {
+            let square = part.clone().mul(part.clone());
+            assert!(is_zero(square.grade(2)) && is_zero(part.clone().wedge(part.clone())));
+        }
+        assert!(is_zero(
+            b1.clone().mul(b2.clone()).sub(b2.clone().mul(b1.clone()))
+        ));
+        assert!(is_zero(b1.add(b2).sub(b)));
+    }
+
+    #[test]
+    fn test_split() -> syn::Result<()> {
+        // a screw, an euclidean line plus an ideal one
+        let pga3d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1), ("e3", 1)])?;
+        check_split(pga3d.mv([("e12", 1.0), ("e23", 0.5), ("e03", 2.0), ("e01", -1.0)])?);
+        // a boost and a rotation in spacetime
+        let sta = Algebra::new([("e0", 1), ("e1", -1), ("e2", -1), ("e3", -1)])?;
+        check_split(sta.mv([("e01", 0.7), ("e23", 1.3), ("e02", 0.2), ("e12", -0.4)])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flip_and_grades() -> syn::Result<()> {
+        let vga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let mv = vga3d.mv([("scalar", 1.0), ("e1", 1.0), ("e12", 1.0), ("e123", 1.0)])?;
+        let rev = format!("{}", mv.clone().rev());
+        assert_eq!(format!("{}", mv.clone().flip(&[2, 3])), rev);
+        let conj = format!("{}", mv.clone().conj());
+        assert_eq!(format!("{}", mv.clone().flip(&[1, 2])), conj);
+        assert_eq!(mv.clone().even().into_iter().count(), 2);
+        assert_eq!(get(&vga3d, &mv.clone().odd(), "e123"), 1.0);
+        let squares = vga3d.squares();
+        let range = |src: &str| squares.parse_grade_range(&syn::parse_str(src).unwrap());
+        assert_eq!(range("1..=2")?, 1..=2);
+        assert_eq!(range("1..3")?, 1..=2);
+        assert_eq!(range("2..")?, 2..=3);
+        assert!(range("..0").is_err());
+        assert!(range("0..=4").is_err());
+        let middle = mv.grade_range(range("1..=2")?);
+        assert_eq!(middle.into_iter().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_complements() -> syn::Result<()> {
+        let pga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1), ("e4", 0)])?;
+        for name in ["scalar", "e4", "e23", "e41", "e321", "e1234"] {
+            let blade = pga3d.mv([(name, 1.0)])?;
+            let right = blade.clone().right_complement();
+            let left = blade.clone().left_complement();
+            assert_eq!(
+                get(&pga3d, &blade.clone().wedge(right.clone()), "e1234"),
+                1.0
+            );
+            assert_eq!(get(&pga3d, &left.wedge(blade.clone()), "e1234"), 1.0);
+            assert_eq!(get(&pga3d, &right.left_complement(), name), 1.0);
+        }
+        // e4 ^ e123 == -e1234
+        let e4 = pga3d.mv([("e4", 1.0)])?;
+        assert_eq!(get(&pga3d, &e4.clone().right_complement(), "e123"), -1.0);
+        // e4 is all weight and e123 all bulk
+        assert_eq!(e4.clone().bulk_right_complement().into_iter().count(), 0);
+        assert_eq!(get(&pga3d, &e4.weight_right_complement(), "e123"), -1.0);
+        let e123 = pga3d.mv([("e123", 1.0)])?;
+        assert_eq!(e123.clone().weight_left_complement().into_iter().count(), 0);
+        assert_eq!(get(&pga3d, &e123.bulk_left_complement(), "e4"), -1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hodge() -> syn::Result<()> {
+        let sta = Algebra::new([("e0", -1), ("e1", 1), ("e2", 1), ("e3", 1)])?;
+        for name in ["e0", "e1", "e01", "e23", "e123"] {
+            let blade = sta.mv([(name, 1.0)])?;
+            let square = get(&sta, &blade.clone().mul(blade.clone().rev()), "scalar");
+            let hodge = blade.clone().hodge();
+            assert_eq!(get(&sta, &blade.wedge(hodge), "e0123"), square);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_squares() {
+        assert!(Algebra::new([("e1", 2)]).is_err());
+        assert!(Algebra::new([("e1", 1), ("e1", -1)]).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+// coefficients are indexed by the bits of their blade, bit `i` for the `i`th axis in order,
+// products use the euclidean metric since spans don't depend on the metric
+
+/// the sign of reordering the product of two basis blades into axis order
+fn sign(lhs: usize, rhs: usize) -> Field {
+    let (mut lhs, mut swaps) = (lhs >> 1, 0);
+    while lhs != 0 {
+        swaps += (lhs & rhs).count_ones();
+        lhs >>= 1;
+    }
+    if swaps % 2 == 0 { 1.0 } else { -1.0 }
+}
+
+/// the coefficients of a whole basis, `[Field; N]` in the reified fns so nothing is allocated
+/// and a `Vec` in the evaluator, where the dimension is only known at runtime
+pub trait Coefficients: Clone + AsRef<[Field]> + AsMut<[Field]> {
+    fn zero(&self) -> Self {
+        let mut zero = self.clone();
+        zero.as_mut().fill(0.0);
+        zero
+    }
+}
+impl<const N: usize> Coefficients for [Field; N] {}
+impl Coefficients for Vec<Field> {}
+
+fn wedge<B: Coefficients>(lhs: &B, rhs: &B) -> B {
+    let mut out = lhs.zero();
+    let slots = out.as_mut();
+    for (i, a) in lhs.as_ref().iter().enumerate() {
+        for (j, b) in rhs.as_ref().iter().enumerate() {
+            if i & j == 0 {
+                slots[i | j] += sign(i, j) * a * b;
+            }
+        }
+    }
+    out
+}
+
+fn lcontract<B: Coefficients>(lhs: &B, rhs: &B) -> B {
+    let mut out = lhs.zero();
+    let slots = out.as_mut();
+    for (i, a) in lhs.as_ref().iter().enumerate() {
+        for (j, b) in rhs.as_ref().iter().enumerate() {
+            if i & j == i {
+                slots[i ^ j] += sign(i, j) * a * b;
+            }
+        }
+    }
+    out
+}
+
+fn norm(x: &[Field]) -> Field {
+    x.iter().map(|x| x * x).sum::<Field>().sqrt()
+}
+
+/// the reverse over the squared norm, only the inverse of blades
+fn inverse<B: Coefficients>(x: &B) -> B {
+    let norm2 = norm(x.as_ref()) * norm(x.as_ref());
+    let mut inverse = x.clone();
+    for (i, x) in inverse.as_mut().iter_mut().enumerate() {
+        let reverse = match i.count_ones() % 4 {
+            0 | 1 => 1.0,
+            _ => -1.0,
+        };
+        *x *= reverse / norm2;
+    }
+    inverse
+}
+
+/// the blade with the largest coefficient, `None` for zero
+fn largest(x: &[Field]) -> Option<usize> {
+    let i = (0..x.len()).max_by(|&i, &j| x[i].abs().total_cmp(&x[j].abs()))?;
+    (x[i] != 0.0).then_some(i)
+}
+
+/// calls `f` on vectors whose wedge is `blade`, projecting the axes of its largest coefficient
+/// onto it. scalars have no factors, and the last factor is what's left of the blade
+pub fn for_each_factor<B: Coefficients>(blade: &B, mut f: impl FnMut(B)) {
+    let Some(largest) = largest(blade.as_ref()).filter(|&largest| largest != 0) else {
+        return;
+    };
+    let last = largest.ilog2();
+    let mut rest = blade.clone();
+    for bit in (0..last).filter(|bit| largest >> bit & 1 == 1) {
+        let mut axis = blade.zero();
+        axis.as_mut()[1 << bit] = 1.0;
+        let factor = lcontract(&lcontract(&axis, &rest), &inverse(&rest));
+        rest = lcontract(&inverse(&factor), &rest);
+        f(factor);
+    }
+    f(rest);
+}
+
+/// the smallest blade containing both, `lhs ^ rhs` when they're disjoint and zero if either is.
+/// a factor of `rhs` is only wedged on when that keeps `Field::EPSILON.sqrt()` of the norms,
+/// so factors within rounding of the join so far count as contained in it
+pub fn join<B: Coefficients>(lhs: &B, rhs: &B) -> B {
+    let tolerance = Field::EPSILON.sqrt();
+    if largest(lhs.as_ref()).is_none() || largest(rhs.as_ref()).is_none() {
+        return lhs.zero();
+    }
+    let mut join = lhs.clone();
+    for_each_factor(rhs, |factor| {
+        let wedge = wedge(&join, &factor);
+        let norms = norm(join.as_ref()) * norm(factor.as_ref());
+        if norm(wedge.as_ref()) > tolerance * norms {
+            join = wedge;
+        }
+    });
+    join
+}
+
+/// the largest blade contained in both, as the dual of `rhs` in their join contracted onto `lhs`
+pub fn meet<B: Coefficients>(lhs: &B, rhs: &B) -> B {
+    let join = join(lhs, rhs);
+    if largest(join.as_ref()).is_none() {
+        return join;
+    }
+    lcontract(&lcontract(rhs, &inverse(&join)), lhs)
+}
+
+/// the factors of `blade` as exactly `K` vectors, zeros for a zero blade
+pub fn factors<const N: usize, const K: usize>(blade: &[Field; N]) -> [[Field; N]; K] {
+    let mut factors = [[0.0; N]; K];
+    let mut slots = factors.iter_mut();
+    for_each_factor(blade, |factor| {
+        if let Some(slot) = slots.next() {
+            *slot = factor
+        }
+    });
+    factors
+}
@@ -1,7 +1,8 @@
 use crate::{
     err,
     geometry::{One, Zero},
-    traits::Squareroot,
+    shader::Lang,
+    traits::{RecipOrZero, Squareroot},
 };
 pub use cas_compute::symbolic::expr::{Primary, SymExpr};
 use itertools::Itertools;
@@ -24,6 +25,13 @@ fn cas_into_syn(cas: SymExpr) -> syn::Result<syn::Expr> {
                 ty: syn::parse_quote!(Field),
             }))
         }
+        SymExpr::Primary(Primary::Call(name, mut args)) if name == RECIP_OR_ZERO => {
+            let x = cas_into_syn(args.remove(0))?;
+            Ok(syn::parse_quote! {{
+                let x: Field = #x;
+                if x == 0 as Field { 0 as Field } else { 1 as Field / x }
+            }})
+        }
         SymExpr::Primary(_) => Ok(syn::parse_str(cas.to_string().replace("__", ".").as_str())?),
         SymExpr::Add(exprs) => match exprs.into_iter().map(cas_into_syn).process_results(|it| {
             it.reduce(|a, b| {
@@ -57,29 +65,15 @@ fn cas_into_syn(cas: SymExpr) -> syn::Result<syn::Expr> {
     }
 }
 fn cas_exp_into_syn(base: SymExpr, power: SymExpr) -> syn::Result<syn::Expr> {
-    match power {
-        // _ => {
-        //     let ex: syn::Expr = syn::parse_str(power.to_string().as_str())?;
-        //     Ok(syn::Expr::MethodCall(syn::ExprMethodCall {
-        //         attrs: Default::default(),
-        //         receiver: Box::new(cas2syn(base)?),
-        //         dot_token: Default::default(),
-        //         turbofish: None,
-        //         method: format_ident!("pow"),
-        //         paren_token: Default::default(),
-        //         args: std::iter::once(ex).collect(),
-        //     }))
-        // }
-        _ => Ok(syn::Expr::MethodCall(syn::ExprMethodCall {
-            attrs: Default::default(),
-            receiver: Box::new(cas_into_syn(base)?),
-            dot_token: Default::default(),
-            turbofish: None,
-            method: format_ident!("pow"),
-            paren_token: Default::default(),
-            args: std::iter::once(cas_into_syn(power)?).collect(),
-        })),
-    }
+    Ok(syn::Expr::MethodCall(syn::ExprMethodCall {
+        attrs: Default::default(),
+        receiver: Box::new(cas_into_syn(base)?),
+        dot_token: Default::default(),
+        turbofish: None,
+        method: format_ident!("pow"),
+        paren_token: Default::default(),
+        args: std::iter::once(cas_into_syn(power)?).collect(),
+    }))
 }
 
 impl TryFrom<CasExpr> for syn::Expr {
@@ -106,6 +100,85 @@ impl CasExpr {
     }
 }
 
+impl CasExpr {
+    /// shader source for the expression, small integer powers are expanded into products
+    /// since `pow` is undefined for negative bases on the GPU
+    pub fn to_shader(&self, lang: Lang) -> String {
+        shader_fmt(&self.0, lang)
+    }
+}
+fn const_f64(expr: &SymExpr) -> Option<f64> {
+    match expr {
+        SymExpr::Primary(Primary::Integer(n)) => Some(n.to_f64()),
+        SymExpr::Primary(Primary::Float(n)) => Some(n.to_f64()),
+        SymExpr::Primary(_) => None,
+        SymExpr::Add(terms) => terms.iter().map(const_f64).sum(),
+        SymExpr::Mul(factors) => factors.iter().map(const_f64).product(),
+        SymExpr::Exp(base, power) => Some(const_f64(base)?.powf(const_f64(power)?)),
+    }
+}
+fn shader_fmt(expr: &SymExpr, lang: Lang) -> String {
+    if let Some(value) = const_f64(expr) {
+        return lang.float_lit(value);
+    }
+    let join = |exprs: Vec<String>, sep: &str| match exprs.len() {
+        1 => exprs.into_iter().next().unwrap(),
+        _ => format!("({})", exprs.join(sep)),
+    };
+    match expr {
+        // `self__e01` is the `e01` field of `self`
+        SymExpr::Primary(Primary::Symbol(sym)) => match sym.split_once("__") {
+            Some((arg, field)) => format!("{}.{field}", lang.ident(arg)),
+            None => lang.ident(sym),
+        },
+        SymExpr::Primary(Primary::Call(name, args)) if name == RECIP_OR_ZERO => {
+            lang.recip_or_zero(&shader_fmt(&args[0], lang))
+        }
+        SymExpr::Primary(Primary::Call(name, args)) => {
+            let args = args.iter().map(|arg| shader_fmt(arg, lang));
+            format!("{name}({})", args.format(", "))
+        }
+        SymExpr::Primary(_) => unreachable!("numbers are constant"),
+        SymExpr::Add(terms) => {
+            let terms = terms.iter().filter(|term| const_f64(term) != Some(0.0));
+            join(terms.map(|term| shader_fmt(term, lang)).collect(), " + ")
+        }
+        SymExpr::Mul(factors) => {
+            // fold the constant factors into a single coefficient
+            let (consts, factors): (Vec<_>, Vec<_>) = factors
+                .iter()
+                .partition(|factor| const_f64(factor).is_some());
+            let coeff: f64 = consts.into_iter().filter_map(const_f64).product();
+            let mut factors = factors
+                .into_iter()
+                .map(|factor| shader_fmt(factor, lang))
+                .collect_vec();
+            if coeff.abs() != 1.0 {
+                factors.insert(0, lang.float_lit(coeff.abs()));
+            }
+            match coeff < 0.0 {
+                true => format!("(-{})", join(factors, " * ")),
+                false => join(factors, " * "),
+            }
+        }
+        SymExpr::Exp(base, power) => {
+            let base = shader_fmt(base, lang);
+            match const_f64(power) {
+                Some(0.5) => format!("sqrt({base})"),
+                Some(-0.5) => format!("{}({base})", lang.inverse_sqrt()),
+                Some(n) if n.fract() == 0.0 && (1.0..=4.0).contains(&n.abs()) => {
+                    let product = vec![base; n.abs() as usize].join(" * ");
+                    match n > 0.0 {
+                        true => format!("({product})"),
+                        false => format!("(1.0 / ({product}))"),
+                    }
+                }
+                _ => format!("pow({base}, {})", shader_fmt(power, lang)),
+            }
+        }
+    }
+}
+
 impl CasExpr {
     pub fn int<T: Into<rug::Integer>>(n: T) -> CasExpr {
         CasExpr(cas_compute::symbolic::expr::SymExpr::Primary(
@@ -177,6 +250,20 @@ impl Squareroot for CasExpr {
         Self(self.0.sqrt())
     }
 }
+/// the call `RecipOrZero` stands for until it's turned into a branch in code
+const RECIP_OR_ZERO: &str = "recip_or_zero";
+impl RecipOrZero for CasExpr {
+    fn recip_or_zero(self) -> Self {
+        match const_f64(&self.0) {
+            Some(0.0) => Self::zero(),
+            Some(_) => Self::one() / self,
+            None => Self(SymExpr::Primary(Primary::Call(
+                RECIP_OR_ZERO.into(),
+                vec![self.0],
+            ))),
+        }
+    }
+}
 impl Neg for CasExpr {
     type Output = Self;
     fn neg(self) -> Self::Output {
@@ -307,6 +394,20 @@ mod tests {
         assert_eq!(lhs.simplify(), rhs.simplify())
     }
 
+    #[test]
+    fn test_recip_or_zero() {
+        assert_eq!(CasExpr::int(0).recip_or_zero(), CasExpr::zero());
+        let half = CasExpr::int(2).recip_or_zero().simplify();
+        assert_eq!(half, (CasExpr::int(1) / CasExpr::int(2)).simplify());
+        // only a runtime branch can tell for symbols
+        let x = CasExpr::var("x").recip_or_zero();
+        let expr = syn::Expr::try_from(x)
+            .unwrap()
+            .to_token_stream()
+            .to_string();
+        assert!(expr.contains("if x == 0 as Field"), "{expr}");
+    }
+
     #[test]
     fn test_asym() {
         let a_e0 = CasExpr::var("a__e0");
@@ -0,0 +1,117 @@
+//! helpers for the tests generated by `#[reefer::algebraic(selftest)]`
+use crate::{algebra::Algebra, geometry::Mvect};
+
+/// random inputs per generated test
+pub const ROUNDS: usize = 16;
+/// relative tolerance, loose enough for `f32` fields
+pub const TOLERANCE: f64 = 1e-4;
+
+/// xorshift64*, seeded the same every run so failures reproduce
+pub struct Rng(u64);
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl Rng {
+    /// uniform in `[-1, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let bits = self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11;
+        bits as f64 / (1u64 << 52) as f64 - 1.0
+    }
+    pub fn array<const N: usize>(&mut self) -> [f64; N] {
+        std::array::from_fn(|_| self.next_f64())
+    }
+}
+
+/// panics unless every component of `actual` matches `expected`, blades missing from `actual` must be zero
+pub fn assert_close(alg: &Algebra, expected: &Mvect<'_, f64>, actual: &[(&str, f64)], label: &str) {
+    let actual_mv = alg
+        .mv(actual.iter().copied())
+        .unwrap_or_else(|e| panic!("{label}: {e}"));
+    let scale = expected
+        .clone()
+        .into_iter()
+        .fold(1.0, |scale: f64, (_, v)| scale.max(v.abs()));
+    let diff = expected.clone().sub(actual_mv.clone());
+    if let Some((canon, v)) = diff.into_iter().find(|(_, v)| v.abs() > TOLERANCE * scale) {
+        panic!(
+            "{label}: generated code disagrees with the reference by {v} on {canon}\n  expected: {expected}\n  actual:   {actual_mv}"
+        )
+    }
+}
+
+/// `assert_close` on each part of the output, the parts of a tuple or array or the entries
+/// of a matrix, a single part keeps the label as is
+pub fn assert_parts_close(
+    alg: &Algebra,
+    expected: &[Mvect<'_, f64>],
+    actual: &[&[(&str, f64)]],
+    label: &str,
+) {
+    if expected.len() != actual.len() {
+        panic!(
+            "{label}: the reference has {} parts but the generated code {}",
+            expected.len(),
+            actual.len()
+        )
+    }
+    let single = expected.len() == 1;
+    for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+        match single {
+            true => assert_close(alg, expected, actual, label),
+            false => assert_close(alg, expected, actual, &format!("{label}, part {i}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_range() {
+        let mut rng = Rng::default();
+        let values: [f64; 64] = rng.array();
+        assert!(values.iter().all(|v| (-1.0..1.0).contains(v)));
+        assert!(values.windows(2).all(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn test_assert_close() -> syn::Result<()> {
+        let vga2d = Algebra::new([("e1", 1), ("e2", 1)])?;
+        let expected = vga2d.mv([("e1", 1.0), ("e12", 0.0)])?;
+        assert_close(&vga2d, &expected, &[("e1", 1.0 + 1e-6)], "close");
+        let result = std::panic::catch_unwind(|| {
+            assert_close(&vga2d, &expected, &[("e1", 1.0), ("e2", 0.5)], "far")
+        });
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_parts_close() -> syn::Result<()> {
+        let vga2d = Algebra::new([("e1", 1), ("e2", 1)])?;
+        let expected = [vga2d.mv([("e1", 1.0)])?, vga2d.mv([("e12", 2.0)])?];
+        assert_parts_close(
+            &vga2d,
+            &expected,
+            &[&[("e1", 1.0)], &[("e12", 2.0)]],
+            "close",
+        );
+        let result = std::panic::catch_unwind(|| {
+            assert_parts_close(&vga2d, &expected, &[&[("e1", 1.0)]], "missing")
+        });
+        assert!(result.is_err());
+        let result = std::panic::catch_unwind(|| {
+            assert_parts_close(&vga2d, &expected, &[&[("e1", 1.0)], &[("e12", 1.0)]], "far")
+        });
+        assert!(result.is_err());
+        Ok(())
+    }
+}
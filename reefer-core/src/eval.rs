@@ -0,0 +1,364 @@
+//! numeric evaluation of reified bodies, the reference the generated code is checked against
+use crate::{
+    algebra::Algebra,
+    err,
+    geometry::{Blade, BladeValue, Blank, ConstOne, Mvect},
+};
+use syn::punctuated::Punctuated;
+
+/// evaluate the source of a reified body with the given arguments bound
+///     eval_str(&alg, "(self ^ line).dual()", &[("self", a), ("line", b)])
+pub fn eval_str<'a>(
+    alg: &'a Algebra,
+    src: &str,
+    env: &[(&str, Mvect<'a, f64>)],
+) -> syn::Result<Mvect<'a, f64>> {
+    Evaluator::new(alg, env).eval(&syn::parse_str(src)?)
+}
+
+/// `eval_str` with `[[Field; DIM]; DIM]` args bound as well, returning the parts of the output
+///     eval_parts_str(&alg, "self.outermorphism(m).split()", &[("self", b)], &[("m", m)])
+pub fn eval_parts_str<'a>(
+    alg: &'a Algebra,
+    src: &str,
+    env: &[(&str, Mvect<'a, f64>)],
+    matrices: &[(&str, Vec<Vec<f64>>)],
+) -> syn::Result<Vec<Mvect<'a, f64>>> {
+    Evaluator::new(alg, env)
+        .with_matrices(matrices)
+        .eval_parts(&syn::parse_str(src)?)
+}
+
+pub struct Evaluator<'a, 'e> {
+    alg: &'a Algebra,
+    env: &'e [(&'e str, Mvect<'a, f64>)],
+    matrices: &'e [(&'e str, Vec<Vec<f64>>)],
+}
+
+impl<'a, 'e> Evaluator<'a, 'e> {
+    pub fn new(alg: &'a Algebra, env: &'e [(&'e str, Mvect<'a, f64>)]) -> Self {
+        Self {
+            alg,
+            env,
+            matrices: &[],
+        }
+    }
+    /// binds the `[[Field; DIM]; DIM]` args, which `outermorphism` takes
+    pub fn with_matrices(self, matrices: &'e [(&'e str, Vec<Vec<f64>>)]) -> Self {
+        Self { matrices, ..self }
+    }
+    /// the parts of a `split()` or `factorize()` tail, the entries of a `to_matrix()` tail row
+    /// by row as scalars, or the value of any other expression as the only part
+    pub fn eval_parts(&self, expr: &syn::Expr) -> syn::Result<Vec<Mvect<'a, f64>>> {
+        let syn::Expr::MethodCall(call) = expr else {
+            return Ok(vec![self.eval(expr)?]);
+        };
+        Ok(match call.method.to_string().as_str() {
+            "split" if call.args.is_empty() => self.eval(&call.receiver)?.split(),
+            "factorize" if call.args.is_empty() => self.eval(&call.receiver)?.factorize(),
+            "to_matrix" if call.args.is_empty() => {
+                let m = self.eval(&call.receiver)?.to_matrix();
+                m.into_iter().flatten().map(|v| self.scalar(v)).collect()
+            }
+            _ => vec![self.eval(expr)?],
+        })
+    }
+    /// mirrors the operators and builtin methods the reifier expands
+    pub fn eval(&self, expr: &syn::Expr) -> syn::Result<Mvect<'a, f64>> {
+        match expr {
+            syn::Expr::Paren(expr) => self.eval(&expr.expr),
+            syn::Expr::Group(expr) => self.eval(&expr.expr),
+            syn::Expr::Lit(syn::ExprLit { lit, .. }) => {
+                let value = match lit {
+                    syn::Lit::Int(n) => n.base10_parse()?,
+                    syn::Lit::Float(n) => n.base10_parse()?,
+                    lit => return Err(err!(lit, "expected a number")),
+                };
+                Ok(self.scalar(value))
+            }
+            syn::Expr::Path(path) => {
+                let Some(ident) = path.path.get_ident() else {
+                    return Err(err!(path, "expected a variable"));
+                };
+                match self.env.iter().find(|(name, _)| ident == name) {
+                    Some((_, mv)) => Ok(mv.clone()),
+                    None => Err(err!(ident, format!("unknown variable `{ident}`"))),
+                }
+            }
+            syn::Expr::Field(field) => {
+                let syn::Member::Named(ident) = &field.member else {
+                    return Err(err!(field.member, "expected a blade"));
+                };
+                let blank: Blank = ident.clone().try_into()?;
+                let mut mv = self.eval(&field.base)?;
+                let value = mv.take(&blank.hone(self.alg.squares())).unwrap_or(0.0);
+                Ok(self.scalar(value))
+            }
+            syn::Expr::Index(index) => Ok(self
+                .eval(&index.expr)?
+                .component(&self.basis_blade(&index.index)?)),
+            syn::Expr::Unary(unary) => {
+                let mv = self.eval(&unary.expr)?;
+                match unary.op {
+                    syn::UnOp::Neg(_) => Ok(mv.neg()),
+                    syn::UnOp::Not(_) => Ok(mv.dual(self.alg.ps())),
+                    op => Err(err!(op, "unsupported operator")),
+                }
+            }
+            syn::Expr::Binary(bin) => {
+                let lhs = self.eval(&bin.left)?;
+                let rhs = self.eval(&bin.right)?;
+                match bin.op {
+                    syn::BinOp::Add(_) => Ok(lhs.add(rhs)),
+                    syn::BinOp::Sub(_) => Ok(lhs.sub(rhs)),
+                    syn::BinOp::Mul(_) => Ok(lhs.mul(rhs)),
+                    syn::BinOp::Div(_) => Ok(lhs.div(rhs)),
+                    syn::BinOp::BitXor(_) => Ok(lhs.wedge(rhs)),
+                    syn::BinOp::BitOr(_) => Ok(lhs.fat_dot(rhs)),
+                    syn::BinOp::BitAnd(_) => Ok(lhs.regressive(rhs)),
+                    syn::BinOp::Shl(_) => Ok(lhs.lcontract(rhs)),
+                    syn::BinOp::Shr(_) => Ok(lhs.rcontract(rhs)),
+                    syn::BinOp::Rem(_) => Ok(lhs.sandwich(rhs)),
+                    // componentwise, blades missing from either side compare against zero
+                    syn::BinOp::Eq(_) => {
+                        Ok(self.truth(lhs.sub(rhs).into_iter().all(|(_, v)| v == 0.0)))
+                    }
+                    syn::BinOp::Ne(_) => {
+                        Ok(self.truth(lhs.sub(rhs).into_iter().any(|(_, v)| v != 0.0)))
+                    }
+                    op @ (syn::BinOp::Lt(_)
+                    | syn::BinOp::Le(_)
+                    | syn::BinOp::Gt(_)
+                    | syn::BinOp::Ge(_)) => {
+                        // only scalars are ordered
+                        let lhs = self.ordered(lhs, &bin.left)?;
+                        let rhs = self.ordered(rhs, &bin.right)?;
+                        Ok(self.truth(match op {
+                            syn::BinOp::Lt(_) => lhs < rhs,
+                            syn::BinOp::Le(_) => lhs <= rhs,
+                            syn::BinOp::Gt(_) => lhs > rhs,
+                            _ => lhs >= rhs,
+                        }))
+                    }
+                    op => Err(err!(op, "unsupported operator")),
+                }
+            }
+            syn::Expr::MethodCall(call) => self.eval_method(call),
+            syn::Expr::Macro(mac) if mac.mac.path.is_ident("mv") => {
+                let values = mac.mac.parse_body_with(
+                    Punctuated::<BladeValue, syn::Token![,]>::parse_separated_nonempty,
+                )?;
+                values.into_iter().try_fold(self.empty(), |mv, bv| {
+                    let value = self.eval(&bv.expr)?.get(&Blade::One).unwrap_or(0.0);
+                    Ok(mv.add_blade_value(bv.blade.hone(self.alg.squares()), value))
+                })
+            }
+            expr => Err(err!(expr, "cannot evaluate expression")),
+        }
+    }
+    fn eval_method(&self, call: &syn::ExprMethodCall) -> syn::Result<Mvect<'a, f64>> {
+        let rec = self.eval(&call.receiver)?;
+        let arg = |n: usize| match call.args.iter().nth(n) {
+            Some(arg) => self.eval(arg),
+            None => Err(err!(call, "missing argument")),
+        };
+        let lit = || match call.args.first() {
+            Some(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(n),
+                ..
+            })) => n.base10_parse::<usize>(),
+            _ => Err(err!(call.args, "expected usize")),
+        };
+        let ps = || match call.args.first() {
+            None => Ok(self.alg.ps()),
+            Some(syn::Expr::Path(path)) => match path.path.get_ident() {
+                Some(ident) => Ok(ident.to_string().parse::<Blank>()?.hone(self.alg.squares())),
+                None => Err(err!(path, "unrecognized psuedoscalar")),
+            },
+            Some(arg) => Err(err!(arg, "unrecognized psuedoscalar")),
+        };
+        Ok(match call.method.to_string().as_str() {
+            "commutate" => rec.commutate(arg(0)?),
+            "anticomm" => rec.anticomm(arg(0)?),
+            "sandwich" => rec.sandwich(arg(0)?),
+            "project" => rec.project(arg(0)?),
+            "reject" => rec.reject(arg(0)?),
+            "reflect" => rec.reflect(arg(0)?),
+            "antimul" => rec.antimul(arg(0)?),
+            "antiwedge" => rec.antiwedge(arg(0)?),
+            "antidot" => rec.antidot(arg(0)?),
+            "antisandwich" => rec.antisandwich(arg(0)?),
+            "inverse" => rec.inv(),
+            "grade" => rec.grade(lit()?),
+            "grade_range" => match call.args.first() {
+                Some(range) => rec.grade_range(self.alg.squares().parse_grade_range(range)?),
+                None => return Err(err!(call, "missing argument")),
+            },
+            "flip" => rec.flip(&self.alg.squares().parse_grades(&call.args)?),
+            "even" => rec.even(),
+            "odd" => rec.odd(),
+            "pow" => rec.pow(lit()?),
+            "norm" => rec.norm(),
+            "normed" => rec.normed(),
+            "bulk" => rec.bulk(),
+            "weight" => rec.weight(),
+            "bulk_norm" => rec.bulk_norm(),
+            "weight_norm" => rec.weight_norm(),
+            "unitize" => rec.unitize(),
+            // only the single component case is implemented
+            "sqrt" if rec.clone().into_iter().count() <= 1 => rec.sqrt(),
+            "add" => rec.add(arg(0)?),
+            "sub" => rec.sub(arg(0)?),
+            "mul" => rec.mul(arg(0)?),
+            "wedge" => rec.wedge(arg(0)?),
+            "regressive" => rec.regressive(arg(0)?),
+            "join" => rec.join(arg(0)?),
+            "meet" => rec.meet(arg(0)?),
+            "dot" => rec.dot(arg(0)?),
+            "fat_dot" => rec.fat_dot(arg(0)?),
+            "lcontract" => rec.lcontract(arg(0)?),
+            "rcontract" => rec.rcontract(arg(0)?),
+            "dual" => rec.dual(ps()?),
+            "undual" => rec.undual(ps()?),
+            "ldual" => rec.ldual(ps()?),
+            "lundual" => rec.lundual(ps()?),
+            "neg" => rec.neg(),
+            "aut" => rec.aut(),
+            "rev" => rec.rev(),
+            "conj" => rec.conj(),
+            "right_complement" => rec.right_complement(),
+            "left_complement" => rec.left_complement(),
+            "bulk_right_complement" => rec.bulk_right_complement(),
+            "bulk_left_complement" => rec.bulk_left_complement(),
+            "weight_right_complement" => rec.weight_right_complement(),
+            "weight_left_complement" => rec.weight_left_complement(),
+            "hodge" => rec.hodge(),
+            "simplify" => rec,
+            "outermorphism" => match call.args.first() {
+                Some(m) => rec.outermorphism(self.matrix(m)?),
+                None => return Err(err!(call, "missing argument")),
+            },
+            "get" => rec.component(&self.basis_blade(&call.args[0])?),
+            method => {
+                let msg = format!("cannot evaluate method `{method}`");
+                return Err(err!(call.method, msg));
+            }
+        })
+    }
+    fn basis_blade(&self, expr: &syn::Expr) -> syn::Result<Blade> {
+        let syn::Expr::Path(path) = expr else {
+            return Err(err!(expr, "expected a `Basis` blade"));
+        };
+        let ident = &path.path.segments.last().unwrap().ident;
+        let blank: Blank = ident.clone().try_into()?;
+        Ok(blank.hone(self.alg.squares()))
+    }
+    /// a `[[Field; DIM]; DIM]` arg
+    fn matrix(&self, expr: &syn::Expr) -> syn::Result<&'e [Vec<f64>]> {
+        let m = match expr {
+            syn::Expr::Path(path) => self
+                .matrices
+                .iter()
+                .find_map(|(name, m)| path.path.is_ident(name).then_some(m)),
+            _ => None,
+        };
+        m.map(Vec::as_slice)
+            .ok_or_else(|| err!(expr, "expected a `[[Field; DIM]; DIM]` argument"))
+    }
+    /// the value of a scalar, non-scalar blades are an error unless they're zero
+    fn ordered(&self, mut mv: Mvect<'a, f64>, expr: &syn::Expr) -> syn::Result<f64> {
+        let scalar = mv.take(&Blade::One).unwrap_or(0.0);
+        match mv.into_iter().find(|(_, v)| *v != 0.0) {
+            Some((canon, _)) => Err(err!(expr, format!("expected a scalar, found {canon}"))),
+            None => Ok(scalar),
+        }
+    }
+    /// comparisons are 1 when they hold and 0 otherwise
+    fn truth(&self, holds: bool) -> Mvect<'a, f64> {
+        self.scalar(if holds { 1.0 } else { 0.0 })
+    }
+    fn empty(&self) -> Mvect<'a, f64> {
+        Mvect::empty(self.alg.squares())
+    }
+    fn scalar(&self, value: f64) -> Mvect<'a, f64> {
+        Mvect::scalar(value, self.alg.squares())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_str() -> syn::Result<()> {
+        let pga2d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1)])?;
+        let a = pga2d.mv([("e1", 1.0), ("e0", -1.0)])?;
+        let b = pga2d.mv([("e2", 1.0), ("e0", -2.0)])?;
+        let env = [("a", a.clone()), ("b", b.clone())];
+        let meet = eval_str(&pga2d, "(a ^ b).simplify()", &env)?;
+        assert_eq!(format!("{meet}"), format!("{}", a.clone().wedge(b)));
+        let scaled = eval_str(&pga2d, "a * mv!(scalar: -2.0) + 2 * a", &env)?;
+        assert!(scaled.into_iter().all(|(_, v)| v == 0.0));
+        assert_eq!(eval_str(&pga2d, "a.e0", &env)?.get(&Blade::One), Some(-1.0));
+        let e02 = meet.get(&"e02".parse::<Blank>()?.hone(pga2d.squares()));
+        let e20 = eval_str(&pga2d, "(a ^ b)[Basis::e20]", &env)?.get(&Blade::One);
+        assert_eq!(e20, e02.map(|v| -v));
+        assert!(
+            eval_str(&pga2d, "a.get(Basis::e12)", &env)?
+                .into_iter()
+                .next()
+                .is_none()
+        );
+        let projected = eval_str(&pga2d, "a.project(a)", &env)?;
+        assert_eq!(format!("{projected}"), format!("{a}"));
+        assert!(eval_str(&pga2d, "a.flip(b)", &env).is_err());
+        assert!(eval_str(&pga2d, "c", &env).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_eval_comparisons() -> syn::Result<()> {
+        let pga2d = Algebra::new([("e0", 0), ("e1", 1), ("e2", 1)])?;
+        let a = pga2d.mv([("e1", 1.0), ("e0", -1.0)])?;
+        let b = pga2d.mv([("e2", 1.0), ("e0", -2.0)])?;
+        let env = [("a", a), ("b", b)];
+        let truth = |src: &str| eval_str(&pga2d, src, &env).map(|mv| mv.get(&Blade::One));
+        assert_eq!(truth("(a ^ a) == 0.0")?, Some(1.0));
+        assert_eq!(truth("a == b")?, Some(0.0));
+        assert_eq!(truth("a != b")?, Some(1.0));
+        assert_eq!(truth("a.dot(a) > 0.0")?, Some(1.0));
+        assert_eq!(truth("a.dot(b) <= -1.0")?, Some(0.0));
+        assert_eq!(truth("a.dot(b) >= 0.0")?, Some(1.0));
+        assert!(truth("a < b").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_eval_parts() -> syn::Result<()> {
+        let vga3d = Algebra::new([("e1", 1), ("e2", 1), ("e3", 1)])?;
+        let b = vga3d.mv([("e12", 2.0)])?;
+        let v = vga3d.mv([("e1", 1.0)])?;
+        let env = [("b", b.clone()), ("v", v)];
+        let swap = vec![
+            vec![0.0, 1.0, 0.0],
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let matrices = [("m", swap)];
+        let image = eval_parts_str(&vga3d, "v.outermorphism(m)", &env, &matrices)?;
+        let e2 = vga3d.mv([("e2", 1.0)])?;
+        assert!(image[0].clone().sub(e2).into_iter().all(|(_, v)| v == 0.0));
+        assert!(eval_parts_str(&vga3d, "v.outermorphism(n)", &env, &matrices).is_err());
+        let factors = eval_parts_str(&vga3d, "b.factorize()", &env, &[])?;
+        assert_eq!(factors.len(), 2);
+        let wedge = factors[0].clone().wedge(factors[1].clone());
+        assert!(wedge.sub(b).into_iter().all(|(_, v)| v.abs() < 1e-9));
+        // a quarter turn taking e1 to -e2
+        let rotor = "(mv!(scalar: 1.0) + b * 0.5).normed().to_matrix()";
+        let entries = eval_parts_str(&vga3d, rotor, &env, &[])?;
+        let entries = entries.iter().map(|mv| mv.get(&Blade::One).unwrap_or(0.0));
+        let expected = [0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+        assert!(entries.zip(expected).all(|(v, e)| (v - e).abs() < 1e-9));
+        Ok(())
+    }
+}
@@ -23,6 +23,7 @@ impl Mul for Canon {
 }
 impl Mul for Blade {
     type Output = Blunt;
+    #[allow(clippy::suspicious_arithmetic_impl)] // parities combine with xor
     fn mul(self, other: Self) -> Self::Output {
         let (blade_parity, lhs, rhs) = match (self, other) {
             (Blade::Zero, _) => return Blunt::Zero,
@@ -57,6 +58,16 @@ impl From<Squared> for Blade {
         }
     }
 }
+impl From<Squared> for i8 {
+    fn from(value: Squared) -> Self {
+        match value {
+            Squared::Zero => 0,
+            Squared::Pos(()) => 1,
+            Squared::Neg(()) => -1,
+        }
+    }
+}
+
 impl From<Axis> for Blade {
     fn from(value: Axis) -> Self {
         Blade::Pos(Honed(vec![value]))
@@ -140,7 +151,7 @@ impl Squared {
 impl Axis {
     fn square(&self, squares: &SquareMap) -> Squared {
         let err_msg = format!("axis {self} missing square!");
-        squares.0.get(&self).expect(&err_msg).clone()
+        squares.0.get(self).expect(&err_msg).clone()
     }
 }
 
@@ -172,7 +183,7 @@ impl Blunt {
             true => Blade::Neg(canon),
         }
     }
-    pub(crate) fn hone(self, sq: &SquareMap) -> Blade {
+    pub fn hone(self, sq: &SquareMap) -> Blade {
         let (mut parity, frame) = match self {
             Blunt::Zero => return Blade::Zero,
             Blunt::Pos(Sorted(frame)) => (false, frame),
@@ -202,6 +213,8 @@ impl Blunt {
 }
 
 impl Blade {
+    /// the grade of the blade
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         match self {
             Blade::Zero => 0,
@@ -238,6 +251,37 @@ impl Blade {
     }
 }
 
+impl SquareMap {
+    /// every canonical blade, ordered by grade then axis
+    pub fn basis(&self) -> Vec<Canon> {
+        let axes = self.0.keys().copied().sorted().collect_vec();
+        axes.into_iter().powerset().map(Honed).collect()
+    }
+    /// bit `i` for the `i`th axis in order, the index of a blade in `crate::blades`
+    pub fn bits(&self, canon: &Canon) -> usize {
+        let axes = self.0.keys().copied().sorted().collect_vec();
+        canon
+            .0
+            .iter()
+            .map(|axis| 1 << axes.iter().position(|a| a == axis).unwrap())
+            .sum()
+    }
+    /// `basis[i] * basis[j]` as a sign and an index into `basis`, degenerate products are `(0, 0)`
+    pub fn cayley(&self) -> Vec<Vec<(i8, usize)>> {
+        let basis = self.basis();
+        let index = |canon: &Canon| basis.iter().position(|b| b == canon).unwrap();
+        let product = |lhs: &Canon, rhs: &Canon| match (lhs.clone() * rhs.clone()).hone(self) {
+            Blade::Zero => (0, 0),
+            Blade::Pos(canon) => (1, index(&canon)),
+            Blade::Neg(canon) => (-1, index(&canon)),
+        };
+        basis
+            .iter()
+            .map(|lhs| basis.iter().map(|rhs| product(lhs, rhs)).collect())
+            .collect()
+    }
+}
+
 impl Blank {
     pub fn hone(self, sq: &SquareMap) -> Blade {
         Blunt::from(self).hone(sq)
@@ -277,6 +321,41 @@ macro_rules! sq {
 #[cfg(test)]
 mod tests {
     use crate::geometry::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_runtime_squares() {
+        let sta: SquareMap = [(ax!(e0), sq!(1)), (ax!(e1), sq!(-1)), (ax!(e2), sq!(-1))]
+            .into_iter()
+            .collect();
+        assert_eq!(sta.1, bl!(e012));
+        assert_eq!(bl!(e1).pow(2, &sta), -bl!(scalar));
+        let e10: Blank = "e10".parse().unwrap();
+        assert_eq!(e10.hone(&sta), -bl!(e01));
+    }
+
+    #[test]
+    fn test_cayley() {
+        let pga2d: SquareMap = [(ax!(e0), sq!(0)), (ax!(e1), sq!(1)), (ax!(e2), sq!(1))]
+            .into_iter()
+            .collect();
+        let basis = pga2d
+            .basis()
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect_vec();
+        assert_eq!(
+            basis,
+            ["scalar", "e0", "e1", "e2", "e01", "e02", "e12", "e012"]
+        );
+        let cayley = pga2d.cayley();
+        assert_eq!(cayley[0][7], (1, 7)); // scalar * e012
+        assert_eq!(cayley[1][1], (0, 0)); // e0 * e0
+        assert_eq!(cayley[2][3], (1, 6)); // e1 * e2 = e12
+        assert_eq!(cayley[3][2], (-1, 6)); // e2 * e1 = -e12
+        assert_eq!(cayley[6][6], (-1, 0)); // e12 * e12 = -1
+        assert_eq!(cayley[6][1], (1, 7)); // e12 * e0 = e012
+    }
 
     #[test]
     fn test_blade() {
@@ -16,7 +16,7 @@ pub enum Oriented<T> {
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// marker trait for "is sorted, unique, and elements are in canonical form"
-pub struct Honed<T: ?Sized + Clone>(pub(super) T);
+pub struct Honed<T: Clone>(pub(super) T);
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// marker trait for "is sorted"
 pub struct Sorted<T: ?Sized>(pub(super) T);
@@ -67,6 +67,14 @@ impl Default for SquareMap {
     }
 }
 
+/// a `blade: expr` pair, as written in `mv!` bodies
+pub struct BladeValue {
+    pub attrs: Vec<syn::Attribute>,
+    pub blade: Blank,
+    pub colon: syn::token::Colon,
+    pub expr: syn::Expr,
+}
+
 #[derive(Debug, Clone)]
 pub struct Mvect<'a, T>(BTreeMap<Canon, T>, &'a SquareMap);
 
@@ -0,0 +1,799 @@
+use super::{
+    Axis, Blade, BladeValue, Canon, CanonMap, ConstOne, Honed, Mvect, One, Shape, SquareMap,
+    Squared, Zero,
+};
+use crate::{
+    cas::CasExpr,
+    err,
+    geometry::{Battery, Blunt},
+    traits::{RecipOrZero, Squareroot},
+};
+use itertools::{EitherOrBoth, Itertools};
+use proc_macro2::Span;
+use quote::format_ident;
+use std::{
+    collections::btree_map::{self, Entry},
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, DivAssign, Mul, Neg, RangeInclusive},
+};
+use syn::parse_quote;
+
+impl Mvect<'_, CasExpr> {
+    pub fn simplify(self) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .map(|(c, v)| (c, v.simplify()))
+                .filter(|(_, v)| v != &CasExpr::zero())
+                .collect(),
+            self.1,
+        )
+    }
+    pub fn try_into_expr(self, batteries: &CanonMap, span: Span) -> syn::Result<syn::Expr> {
+        let (shape, values) = self.try_into_fields(batteries, span)?;
+        let mut expr = syn::ExprStruct {
+            attrs: vec![],
+            qself: None,
+            path: format_ident!("{shape}").into(),
+            brace_token: Default::default(),
+            fields: Default::default(),
+            dot2_token: None,
+            rest: None,
+        };
+        for (blank, value) in shape.into_iter().zip(values) {
+            expr.fields.push(syn::FieldValue {
+                attrs: vec![],
+                member: syn::Member::Named(format_ident!("{blank}")),
+                colon_token: Some(Default::default()),
+                expr: value.try_into()?,
+            })
+        }
+        Ok(syn::Expr::Struct(expr))
+    }
+    /// the shape of the multivector and the value of each of its fields, in field order
+    pub fn try_into_fields(
+        self,
+        batteries: &CanonMap,
+        span: Span,
+    ) -> syn::Result<(Shape, Vec<CasExpr>)> {
+        let mut mv = self;
+        let sq = mv.1;
+        let shape = mv.try_into_shape(batteries, span)?;
+        let values = shape
+            .clone()
+            .into_iter()
+            .map(|blank| match Blunt::hone(blank.into(), sq) {
+                Blade::Zero => unreachable!(),
+                Blade::Pos(canon) => mv.0.remove(&canon).unwrap_or_else(CasExpr::zero),
+                Blade::Neg(canon) => mv.0.remove(&canon).map_or_else(CasExpr::zero, Neg::neg),
+            })
+            .collect();
+        Ok((shape, values))
+    }
+    pub fn try_into_scalar_expr(self, span: Span) -> syn::Result<syn::Expr> {
+        self.try_into_scalar(span)?.try_into()
+    }
+    pub fn try_into_scalar(mut self, span: Span) -> syn::Result<CasExpr> {
+        let scalar = self.0.remove(&Canon::One).unwrap_or_else(CasExpr::zero);
+        match self.0.keys().next() {
+            Some(canon) => Err(err!(span, format!("expected a scalar, found {canon}"))),
+            None => Ok(scalar),
+        }
+    }
+    /// `a == b` componentwise, blades missing from either side compare against zero
+    pub fn try_into_eq_expr(self, other: Self) -> syn::Result<syn::Expr> {
+        self.try_into_cmp_expr(other, |l, r| parse_quote!((#l) == (#r)))
+    }
+    /// `|a - b| <= eps` componentwise, without requiring `abs` on the field
+    pub fn try_into_approx_eq_expr(self, other: Self, eps: &syn::Expr) -> syn::Result<syn::Expr> {
+        self.try_into_cmp_expr(
+            other,
+            |l, r| parse_quote!((#l) - (#r) <= #eps && (#r) - (#l) <= #eps),
+        )
+    }
+    fn try_into_cmp_expr(
+        self,
+        other: Self,
+        cmp: impl Fn(syn::Expr, syn::Expr) -> syn::Expr,
+    ) -> syn::Result<syn::Expr> {
+        let exprs: Vec<syn::Expr> = self
+            .0
+            .into_iter()
+            .merge_join_by(other.0, |(l, _), (r, _)| l.cmp(r))
+            .map(|both| match both {
+                EitherOrBoth::Both((_, l), (_, r)) => (l.simplify(), r.simplify()),
+                EitherOrBoth::Left((_, l)) => (l.simplify(), CasExpr::zero()),
+                EitherOrBoth::Right((_, r)) => (CasExpr::zero(), r.simplify()),
+            })
+            .filter(|(l, r)| l != r) // symbolically equal already
+            .map(|(l, r)| syn::Result::Ok(cmp(l.try_into()?, r.try_into()?)))
+            .try_collect()?;
+        Ok(
+            match exprs.into_iter().reduce(|a, b| parse_quote!(#a && #b)) {
+                Some(expr) => expr,
+                None => parse_quote!(true),
+            },
+        )
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+impl<T: Display> Display for Mvect<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            self.0
+                .iter()
+                .map(|(canon, value)| format!("{} * {}", value, Blade::from(canon.clone())))
+                .join(" + ")
+                .as_str(),
+        )
+    }
+}
+impl<T: Clone + Neg<Output = T> + Debug + Display> Mvect<'_, T> {
+    pub fn get(&self, blade: &Blade) -> Option<T> {
+        match blade {
+            Blade::Zero => None,
+            Blade::Pos(canon) => self.0.get(canon).cloned(),
+            Blade::Neg(canon) => self.0.get(canon).cloned().map(Neg::neg),
+        }
+    }
+    /// the oriented component along `blade` as a scalar, empty when it is missing
+    pub fn component(&self, blade: &Blade) -> Self {
+        let value = self.get(blade).map(|value| (Canon::One, value));
+        Self(value.into_iter().collect(), self.1)
+    }
+    pub fn take(&mut self, blade: &Blade) -> Option<T> {
+        match blade {
+            Blade::Zero => None,
+            Blade::Pos(canon) => self.0.remove(canon),
+            Blade::Neg(canon) => self.0.remove(canon).map(Neg::neg),
+        }
+    }
+    /// the blades present, declared as a shape or not
+    pub fn battery(&self) -> Battery {
+        self.0.keys().cloned().collect()
+    }
+    pub fn try_into_shape(&self, batts: &CanonMap, span: Span) -> syn::Result<Shape> {
+        if self.0.is_empty() {
+            return Ok(Shape(vec![]));
+        }
+        let batt = self.battery();
+        batts
+            .0
+            .get(&batt)
+            .cloned()
+            .ok_or(err!(span, format!("shape not found: {batt}")))
+    }
+}
+
+// the method names mirror the operators the reifier maps onto them
+#[allow(clippy::should_implement_trait)]
+impl<
+    'a,
+    T: Clone
+        + Debug
+        + AddAssign
+        + DivAssign
+        + Add<Output = T>
+        + Mul<Output = T>
+        + Squareroot<Output = T>
+        + Neg<Output = T>
+        + Zero
+        + One
+        + Squareroot<Output = T>
+        + RecipOrZero
+        + Display,
+> Mvect<'a, T>
+{
+    pub fn add(self, rhs: Self) -> Self {
+        rhs.into_iter()
+            .fold(self, |mv, (canon, value)| mv.add_canon_value(canon, value))
+    }
+    pub fn sub(self, rhs: Self) -> Self {
+        self.add(rhs.neg())
+    }
+    pub fn mul(self, other: Self) -> Self {
+        debug_assert!(std::ptr::eq(self.1, other.1));
+        let sq = self.1;
+        self.into_iter()
+            .cartesian_product(other.into_iter().collect_vec())
+            .fold(Self(Default::default(), sq), |mv, ((lc, lv), (rc, rv))| {
+                mv.add_blade_value((lc * rc).hone(sq), lv * rv)
+            })
+    }
+    pub fn commutate(self, other: Self) -> Self {
+        debug_assert!(std::ptr::eq(self.1, other.1));
+        let sq = self.1;
+        self.into_iter()
+            .cartesian_product(other.into_iter().collect_vec())
+            .fold(Self(Default::default(), sq), |mv, ((lc, lv), (rc, rv))| {
+                let fwd = lc.clone().mul(rc.clone());
+                let rev = rc.mul(lc);
+                if fwd != rev {
+                    mv.add_blade_value(fwd.hone(sq), lv * rv)
+                } else {
+                    mv
+                }
+            })
+    }
+    pub fn anticomm(self, other: Self) -> Self {
+        debug_assert!(std::ptr::eq(self.1, other.1));
+        let sq = self.1;
+        self.into_iter()
+            .cartesian_product(other.into_iter().collect_vec())
+            .fold(Self(Default::default(), sq), |mv, ((lc, lv), (rc, rv))| {
+                let fwd = lc.clone().mul(rc.clone());
+                let rev = rc.mul(lc);
+                if fwd == rev {
+                    mv.add_blade_value(fwd.hone(sq), lv * rv)
+                } else {
+                    mv
+                }
+            })
+    }
+    pub fn sandwich(self, rhs: Self) -> Self {
+        self.clone().mul(rhs).mul(self.rev())
+    }
+    pub fn regressive(self, rhs: Self) -> Self {
+        let ps = &self.1.1;
+        self.dual(ps.clone())
+            .wedge(rhs.dual(ps.clone()))
+            .undual(ps.clone())
+    }
+    pub fn wedge(self, rhs: Self) -> Self {
+        self.graded_product(rhs, |l, r| Some(l + r))
+    }
+    pub fn dot(self, rhs: Self) -> Self {
+        self.graded_product(rhs, |l, r| (l == r).then_some(0))
+    }
+    pub fn fat_dot(self, rhs: Self) -> Self {
+        self.graded_product(rhs, |l, r| Some(r.abs_diff(l)))
+    }
+    pub fn lcontract(self, rhs: Self) -> Self {
+        self.graded_product(rhs, |l, r| if l <= r { Some(r - l) } else { None })
+    }
+    pub fn rcontract(self, rhs: Self) -> Self {
+        self.graded_product(rhs, |l, r| if l >= r { Some(l - r) } else { None })
+    }
+    /// `x.rev() / <x * x.rev()>`, exact for blades and versors where that product is a scalar.
+    /// It isn't guarded, null elements like ideal points of PGA divide by zero into inf/NaN
+    pub fn inv(self) -> Self {
+        let mut rev = self.clone().rev();
+        let n = self.mul(rev.clone()).take(&Blade::One).unwrap_or(T::zero());
+        rev.0.values_mut().for_each(|v| *v /= n.clone());
+        rev
+    }
+    /// the left and right inverses of blades and versors agree
+    pub fn linv(self) -> Self {
+        self.inv()
+    }
+    /// `(x | b) * b.inv()`, the contraction picks the sign for either grade order,
+    /// not finite for a null `b`
+    pub fn project(self, onto: Self) -> Self {
+        self.fat_dot(onto.clone()).mul(onto.inv())
+    }
+    /// `(x ^ b) * b.inv()`, what `project` leaves out, not finite for a null `b`
+    pub fn reject(self, from: Self) -> Self {
+        self.wedge(from.clone()).mul(from.inv())
+    }
+    /// `b * x * b.inv()` with `x` grade involuted for odd `b`, `b` acts as a versor so
+    /// it reflects in what it represents in PGA, a vector in its normal hyperplane.
+    /// Not finite for a null `b`
+    pub fn reflect(self, mirror: Self) -> Self {
+        let x = match mirror.0.keys().all(|canon| canon.0.len() % 2 == 1) {
+            true => self.aut(),
+            false => self,
+        };
+        mirror.clone().mul(x).mul(mirror.inv())
+    }
+    pub fn div(self, rhs: Self) -> Self {
+        self.mul(rhs.inv())
+    }
+    pub fn ldiv(self, rhs: Self) -> Self {
+        self.linv().mul(rhs)
+    }
+    pub fn exp(self) -> Self {
+        todo!()
+    }
+    pub fn log(self) -> Self {
+        todo!()
+    }
+    pub fn sqrt(self) -> Self {
+        if self.0.len() <= 1 {
+            Self(
+                self.0.into_iter().map(|(c, v)| (c, v.sqrt())).collect(),
+                self.1,
+            )
+        } else {
+            todo!()
+        }
+    }
+    pub fn square(self) -> Self {
+        self.clone().mul(self)
+    }
+    pub fn pow(self, n: usize) -> Self {
+        match n {
+            0 => Self([(Canon::One, T::one())].into_iter().collect(), self.1),
+            1 => self,
+            2 => self.square(),
+            n if n % 2 == 0 => self.pow(n / 2).pow(2),
+            n => self.clone().pow(n / 2).pow(2).mul(self),
+        }
+    }
+    pub fn grade(self, n: usize) -> Self {
+        self.grades(|grade| grade == n)
+    }
+    pub fn grade_range(self, range: RangeInclusive<usize>) -> Self {
+        self.grades(|grade| range.contains(&grade))
+    }
+    pub fn even(self) -> Self {
+        self.grades(|grade| grade % 2 == 0)
+    }
+    pub fn odd(self) -> Self {
+        self.grades(|grade| grade % 2 == 1)
+    }
+    pub fn neg(self) -> Self {
+        self.involution(|_| true)
+    }
+    pub fn aut(self) -> Self {
+        self.involution(|grade| grade % 2 == 1)
+    }
+    pub fn rev(self) -> Self {
+        self.involution(|grade| grade % 4 >= 2)
+    }
+    pub fn conj(self) -> Self {
+        self.involution(|grade| (grade + 3) % 4 < 2)
+    }
+    /// negates the given grades, `x.flip(&[2, 3])` is `x.rev()` below 6 dimensions
+    pub fn flip(self, grades: &[usize]) -> Self {
+        self.involution(|grade| grades.contains(&grade))
+    }
+    pub fn normed(mut self) -> Self {
+        let n = self._norm();
+        self.0.values_mut().for_each(|v| *v /= n.clone());
+        self
+    }
+    pub fn norm(self) -> Self {
+        let sq = self.1;
+        Self([(Canon::One, self._norm())].into_iter().collect(), sq)
+    }
+    fn _norm(&self) -> T {
+        self.magnitude(false)
+    }
+    /// the blades that don't square to zero
+    pub fn bulk(self) -> Self {
+        self.part(false)
+    }
+    /// the blades whose complement doesn't square to zero, in PGA the ones with the degenerate axis
+    pub fn weight(self) -> Self {
+        self.part(true)
+    }
+    /// the same as `norm`, the bulk is what it measures
+    pub fn bulk_norm(self) -> Self {
+        let sq = self.1;
+        Self(
+            [(Canon::One, self.magnitude(false))].into_iter().collect(),
+            sq,
+        )
+    }
+    /// also a scalar rather than the antiscalar
+    pub fn weight_norm(self) -> Self {
+        let sq = self.1;
+        Self(
+            [(Canon::One, self.magnitude(true))].into_iter().collect(),
+            sq,
+        )
+    }
+    /// divides by the weight norm, so a PGA point ends up with a unit weight
+    pub fn unitize(mut self) -> Self {
+        let n = self.magnitude(true);
+        self.0.values_mut().for_each(|v| *v /= n.clone());
+        self
+    }
+    fn part(mut self, anti: bool) -> Self {
+        let sq = self.1;
+        self.0
+            .retain(|canon, _| !matches!(Self::square_of(sq, canon, anti), Squared::Zero));
+        self
+    }
+    fn magnitude(&self, anti: bool) -> T {
+        self.0
+            .iter()
+            .fold(T::zero(), |norm, (canon, value)| {
+                match Self::square_of(self.1, canon, anti) {
+                    Squared::Zero => norm,
+                    _ => norm + value.clone() * value.clone(),
+                }
+            })
+            .sqrt()
+    }
+    /// `blade ^ blade.right_complement() == ps` for every basis blade, the complements
+    /// only reorder axes so unlike `dual` they are the same for any metric
+    pub fn right_complement(self) -> Self {
+        self.complement(true)
+    }
+    /// `blade.left_complement() ^ blade == ps`, undoes `right_complement`
+    pub fn left_complement(self) -> Self {
+        self.complement(false)
+    }
+    /// the right complement of the bulk, the blades that don't square to zero
+    pub fn bulk_right_complement(self) -> Self {
+        self.metric(false).right_complement()
+    }
+    pub fn bulk_left_complement(self) -> Self {
+        self.metric(false).left_complement()
+    }
+    /// the right complement of the weight, the blades whose complement doesn't square to zero
+    pub fn weight_right_complement(self) -> Self {
+        self.metric(true).right_complement()
+    }
+    pub fn weight_left_complement(self) -> Self {
+        self.metric(true).left_complement()
+    }
+    /// `a ^ b.hodge() == <a * b.rev()> ps` for blades of the same grade, the bulk right complement
+    pub fn hodge(self) -> Self {
+        self.bulk_right_complement()
+    }
+    fn complement(self, right: bool) -> Self {
+        let ps = &self.1.1;
+        self.0
+            .into_iter()
+            .fold(Self(Default::default(), self.1), |mv, (canon, value)| {
+                let comp = Self::complement_canon(ps, &canon);
+                let wedge = match right {
+                    true => canon * comp.clone(),
+                    false => comp.clone() * canon,
+                };
+                let blade = match (wedge, ps) {
+                    (Blunt::Pos(_), Blade::Pos(_)) | (Blunt::Neg(_), Blade::Neg(_)) => {
+                        Blade::Pos(comp)
+                    }
+                    _ => Blade::Neg(comp),
+                };
+                mv.add_blade_value(blade, value)
+            })
+    }
+    /// scales every blade by its square, or with `anti` by the square of its complement
+    fn metric(self, anti: bool) -> Self {
+        let sq = self.1;
+        self.0
+            .into_iter()
+            .fold(Self(Default::default(), sq), |mv, (canon, value)| {
+                let squared = Self::square_of(sq, &canon, anti);
+                mv.add_blade_value(squared * Blade::from(canon), value)
+            })
+    }
+    /// the square of the blade, or with `anti` of its complement
+    fn square_of(sq: &SquareMap, canon: &Canon, anti: bool) -> Squared {
+        match anti {
+            true => Blade::from(Self::complement_canon(&sq.1, canon)).square(sq),
+            false => Blade::from(canon.clone()).square(sq),
+        }
+    }
+    fn complement_canon(ps: &Blade, canon: &Canon) -> Canon {
+        let (Blade::Pos(Honed(axes)) | Blade::Neg(Honed(axes))) = ps else {
+            unreachable!("the pseudoscalar is never zero")
+        };
+        Honed(
+            axes.iter()
+                .filter(|a| !canon.0.contains(a))
+                .copied()
+                .collect(),
+        )
+    }
+    /// the products of the right complements, mapped back with the left complement
+    pub fn antimul(self, rhs: Self) -> Self {
+        self.right_complement()
+            .mul(rhs.right_complement())
+            .left_complement()
+    }
+    pub fn antiwedge(self, rhs: Self) -> Self {
+        self.right_complement()
+            .wedge(rhs.right_complement())
+            .left_complement()
+    }
+    /// uses the metric dot `<a * b.rev()>`, so the antidot of a unitized point with itself is the antiscalar
+    pub fn antidot(self, rhs: Self) -> Self {
+        self.right_complement()
+            .dot(rhs.right_complement().rev())
+            .left_complement()
+    }
+    pub fn antirev(self) -> Self {
+        self.right_complement().rev().left_complement()
+    }
+    /// `self ⟇ rhs ⟇ self.antirev()`, how motors act on the elements of PGA
+    pub fn antisandwich(self, rhs: Self) -> Self {
+        self.clone().antimul(rhs).antimul(self.antirev())
+    }
+    /// `m[i][j]` is the `i`th axis component of the image of the `j`th axis, in `BASIS`
+    /// order. blades map to the wedge of the images of their axes
+    pub fn outermorphism(self, m: &[Vec<T>]) -> Self {
+        let sq = self.1;
+        let axes = sq.0.keys().copied().sorted().collect_vec();
+        let image = |axis: &Axis| {
+            let j = axes.iter().position(|a| a == axis).unwrap();
+            axes.iter()
+                .zip(m)
+                .fold(Self(Default::default(), sq), |mv, (a, row)| {
+                    mv.add_blade_value(Blade::from(*a), row[j].clone())
+                })
+        };
+        self.0
+            .into_iter()
+            .fold(Self(Default::default(), sq), |mv, (canon, value)| {
+                let scalar = Self([(Canon::One, value)].into_iter().collect(), sq);
+                let blade = canon
+                    .0
+                    .iter()
+                    .fold(scalar, |blade, axis| blade.wedge(image(axis)));
+                mv.add(blade)
+            })
+    }
+    /// the commuting simple parts of a bivector, by the invariant decomposition of
+    /// Roelfs and De Keninck. up to 3 dimensions every bivector is simple already.
+    /// with `S = <B * B>` and `W = B ^ B` the squares of the parts are
+    /// `(S ± sqrt(S^2 - W^2)) / 2`, and `W^2` vanishes when the pseudoscalar is null,
+    /// leaving `S` and `0` without a square root. the closed form holds up to 5 dimensions.
+    /// the second part is divided out and the first is what's left of `B`, so where the
+    /// divisor is zero, for simple, null and isoclinic bivectors, the split is `(B, 0)`
+    pub fn split(self) -> Vec<Self> {
+        let sq = self.1;
+        if sq.0.len() < 4 {
+            return vec![self];
+        }
+        let scalar = |value: T| Self([(Canon::One, value)].into_iter().collect(), sq);
+        let div = |mut mv: Self, by: T| {
+            mv.0.values_mut().for_each(|v| *v /= by.clone());
+            mv
+        };
+        let scale = |mut mv: Self, by: T| {
+            mv.0.values_mut().for_each(|v| *v = v.clone() * by.clone());
+            mv
+        };
+        let s = self
+            .clone()
+            .mul(self.clone())
+            .take(&Blade::One)
+            .unwrap_or(T::zero());
+        let w = self.clone().wedge(self.clone());
+        // B * W / 2, the parts swap their squares in it
+        let bw = div(self.clone().mul(w.clone()).grade(2), T::one() + T::one());
+        match w.clone().mul(w).take(&Blade::One) {
+            None => {
+                let ideal = scale(bw, s.recip_or_zero());
+                vec![self.sub(ideal.clone()), ideal]
+            }
+            Some(w2) => {
+                let root = (s.clone() * s.clone() + -w2).sqrt();
+                let mut lo = s + -root.clone();
+                lo /= T::one() + T::one();
+                let part = scale(scalar(lo).mul(self.clone()).sub(bw), -root.recip_or_zero());
+                vec![self.sub(part.clone()), part]
+            }
+        }
+    }
+    /// the matrix of `v -> self * v * self.rev()` on vectors, laid out as in `outermorphism`
+    pub fn to_matrix(self) -> Vec<Vec<T>> {
+        let sq = self.1;
+        let axes = sq.0.keys().copied().sorted().collect_vec();
+        let images = axes
+            .iter()
+            .map(|axis| {
+                let v = Self([(Honed(vec![*axis]), T::one())].into_iter().collect(), sq);
+                self.clone().sandwich(v)
+            })
+            .collect_vec();
+        axes.iter()
+            .map(|axis| {
+                let blade = Blade::from(*axis);
+                images
+                    .iter()
+                    .map(|image| image.get(&blade).unwrap_or(T::zero()))
+                    .collect()
+            })
+            .collect()
+    }
+    /// the rotor or motor with the matrix `m`, undoes `to_matrix` on even versors.
+    /// `f(b) * b.inv()` summed over the blades that don't square to zero is the versor
+    /// times a scalar, or in PGA a scalar plus pseudoscalar, which is divided out
+    pub fn from_matrix(m: &[Vec<T>], sq: &'a SquareMap) -> Self {
+        let psi = sq
+            .basis()
+            .into_iter()
+            .filter(|canon| !matches!(Self::square_of(sq, canon, false), Squared::Zero))
+            .fold(Self(Default::default(), sq), |psi, canon| {
+                let blade = Self([(canon, T::one())].into_iter().collect(), sq);
+                psi.add(blade.clone().outermorphism(m).mul(blade.inv()))
+            });
+        // psi * psi.rev() is the square of the factor a + b I, which is a^2 + 2ab I when
+        // I squares to zero, and (a + b I)^-1 = (1 - b/a I) / a
+        let mut square = psi.clone().mul(psi.clone().rev());
+        let a = square.take(&Blade::One).unwrap_or(T::zero()).sqrt();
+        let mut b_over_a = square.take(&sq.1).unwrap_or(T::zero());
+        b_over_a /= (T::one() + T::one()) * a.clone() * a.clone();
+        let mut inv = Self([(Canon::One, T::one())].into_iter().collect(), sq)
+            .add_blade_value(sq.1.clone(), -b_over_a);
+        inv.0.values_mut().for_each(|v| *v /= a.clone());
+        psi.mul(inv)
+    }
+    /// the coefficients indexed by `SquareMap::bits`, the layout of `crate::blades`
+    pub fn to_bits(&self) -> Vec<T> {
+        let mut bits = vec![T::zero(); 1 << self.1.0.len()];
+        for (canon, value) in &self.0 {
+            bits[self.1.bits(canon)] = value.clone();
+        }
+        bits
+    }
+    /// the blades of `bits` within `grades`
+    pub fn from_bits(bits: &[T], grades: RangeInclusive<usize>, sq: &'a SquareMap) -> Self {
+        let canons = sq.basis().into_iter();
+        let canons = canons.filter(|canon| grades.contains(&canon.0.len()));
+        Self(
+            canons
+                .map(|canon| {
+                    let value = bits[sq.bits(&canon)].clone();
+                    (canon, value)
+                })
+                .collect(),
+            sq,
+        )
+    }
+    /// the grades of the join of blades of the grades of `self` and `rhs`, from where
+    /// one contains the other up to where they're disjoint
+    pub fn join_grades(&self, rhs: &Self) -> RangeInclusive<usize> {
+        let (Some((lo, hi)), Some((rhs_lo, rhs_hi))) = (self.grade_bounds(), rhs.grade_bounds())
+        else {
+            return RangeInclusive::new(1, 0);
+        };
+        lo.max(rhs_lo)..=(hi + rhs_hi).min(self.1.0.len())
+    }
+    /// the grades of the meet, `grade(a) + grade(b) - grade(a.join(b))`
+    pub fn meet_grades(&self, rhs: &Self) -> RangeInclusive<usize> {
+        let (Some((lo, hi)), Some((rhs_lo, rhs_hi))) = (self.grade_bounds(), rhs.grade_bounds())
+        else {
+            return RangeInclusive::new(1, 0);
+        };
+        (lo + rhs_lo).saturating_sub(self.1.0.len())..=hi.min(rhs_hi)
+    }
+    /// the grade of a blade, `None` for zero or mixed grades
+    pub fn single_grade(&self) -> Option<usize> {
+        self.grade_bounds()
+            .and_then(|(lo, hi)| (lo == hi).then_some(lo))
+    }
+    fn grade_bounds(&self) -> Option<(usize, usize)> {
+        let grades = self.0.keys().map(|canon| canon.0.len());
+        grades.minmax().into_option()
+    }
+    pub fn dual(self, ps: Blade) -> Self {
+        self.undual(ps.rev())
+    }
+    pub fn undual(self, ps: Blade) -> Self {
+        self.0
+            .into_iter()
+            .fold(Self(Default::default(), self.1), |mv, (canon, value)| {
+                let bl = Blade::from(canon).mul(ps.clone());
+                mv.add_blade_value(bl.dual_hone(self.1), value)
+            })
+    }
+    pub fn ldual(self, ps: Blade) -> Self {
+        self.lundual(ps.rev())
+    }
+    pub fn lundual(self, ps: Blade) -> Self {
+        self.0
+            .into_iter()
+            .fold(Self(Default::default(), self.1), |mv, (canon, value)| {
+                let bl = ps.clone().mul(canon.into());
+                mv.add_blade_value(bl.dual_hone(self.1), value)
+            })
+    }
+    pub fn add_blade_value(self, blade: Blade, value: T) -> Self {
+        match blade {
+            Blade::Zero => self,
+            Blade::Pos(canon) => self.add_canon_value(canon, value),
+            Blade::Neg(canon) => self.add_canon_value(canon, -value),
+        }
+    }
+    fn add_canon_value(mut self, canon: Canon, value: T) -> Self {
+        match self.0.entry(canon) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+            Entry::Occupied(mut entry) => {
+                entry.get_mut().add_assign(value);
+            }
+        };
+        self
+    }
+    fn involution(mut self, predicate: impl Fn(usize) -> bool) -> Self {
+        for (canon, value) in self.0.iter_mut() {
+            if predicate(canon.0.len()) {
+                *value = -value.clone()
+            }
+        }
+        self
+    }
+    fn grades(mut self, predicate: impl Fn(usize) -> bool) -> Self {
+        self.0.retain(|canon, _| predicate(canon.0.len()));
+        self
+    }
+    fn graded_product(self, other: Self, grader: fn(usize, usize) -> Option<usize>) -> Self {
+        debug_assert!(std::ptr::eq(self.1, other.1));
+        let sq = self.1;
+        let mut prod = Self(Default::default(), self.1);
+        let lgroups = self.group_by_grade();
+        let rgroups = other.group_by_grade();
+        for ((lg, lbs), (rg, rbs)) in lgroups.into_iter().cartesian_product(rgroups) {
+            if let Some(grade) = grader(lg, rg) {
+                for ((lc, lv), (rc, rv)) in lbs.into_iter().cartesian_product(rbs) {
+                    let partial = (lc * rc).hone(sq);
+                    if partial.len() == grade {
+                        prod = prod.add_blade_value(partial, lv * rv)
+                    }
+                }
+            };
+        }
+        prod
+    }
+}
+
+impl Mvect<'_, f64> {
+    /// the smallest blade containing both blades, see `crate::blades::join`
+    pub fn join(self, rhs: Self) -> Self {
+        let grades = self.join_grades(&rhs);
+        let bits = crate::blades::join(&self.to_bits(), &rhs.to_bits());
+        Self::from_bits(&bits, grades, self.1)
+    }
+    /// the largest blade contained in both blades, see `crate::blades::meet`
+    pub fn meet(self, rhs: Self) -> Self {
+        let grades = self.meet_grades(&rhs);
+        let bits = crate::blades::meet(&self.to_bits(), &rhs.to_bits());
+        Self::from_bits(&bits, grades, self.1)
+    }
+    /// vectors whose wedge is the blade, see `crate::blades::for_each_factor`
+    pub fn factorize(self) -> Vec<Self> {
+        let sq = self.1;
+        let mut factors = vec![];
+        crate::blades::for_each_factor(&self.to_bits(), |bits| {
+            factors.push(Self::from_bits(&bits, 1..=1, sq))
+        });
+        factors
+    }
+}
+
+impl<T: Clone> IntoIterator for Mvect<'_, T> {
+    type IntoIter = btree_map::IntoIter<Canon, T>;
+    type Item = (Canon, T);
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> Mvect<'a, CasExpr> {
+    pub fn from_iter<T: IntoIterator<Item = BladeValue>>(
+        iter: T,
+        sq: &'a SquareMap,
+    ) -> syn::Result<Self> {
+        let mut mv = Self(Default::default(), sq);
+        for bv in iter {
+            mv = mv.add_blade_value(bv.blade.hone(sq), bv.expr.try_into()?)
+        }
+        Ok(mv)
+    }
+}
+
+impl<'a, T: 'a + Clone> Mvect<'a, T> {
+    pub fn empty(sq: &'a SquareMap) -> Self {
+        Self(Default::default(), sq)
+    }
+    pub fn scalar(value: T, sq: &'a SquareMap) -> Self {
+        Self([(Canon::One, value)].into_iter().collect(), sq)
+    }
+    fn group_by_grade(self) -> Vec<(usize, Vec<(Canon, T)>)> {
+        self.into_iter()
+            .chunk_by(|(canon, _value)| canon.0.len())
+            .into_iter()
+            .map(|(n, g)| (n, g.collect()))
+            .collect()
+    }
+}
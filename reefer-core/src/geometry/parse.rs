@@ -1,15 +1,50 @@
 use super::{
-    Axis, Battery, Blade, Blank, Blunt, Canon, ConstOne, Honed, Shape, ShapeFamily, ShapeMap,
-    Sorted, SquareMap, Squared,
+    Axis, Battery, Blade, BladeValue, Blank, Blunt, Canon, ConstOne, Honed, Shape, ShapeFamily,
+    ShapeMap, Sorted, SquareMap, Squared,
 };
 use crate::err;
 use itertools::{Either, Itertools, chain};
-use quote::format_ident;
+use proc_macro2::Literal;
+use quote::{format_ident, quote};
 use std::{
     fmt::{Display, Write},
+    ops::RangeInclusive,
     str::FromStr,
 };
-use syn::{parse::Parse, parse_quote, punctuated::Punctuated, visit::Visit};
+use syn::{RangeLimits, parse::Parse, parse_quote, punctuated::Punctuated, visit::Visit};
+
+impl Parse for BladeValue {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let syn::FieldValue {
+            attrs,
+            member: syn::Member::Named(ident),
+            colon_token: Some(colon),
+            expr,
+        } = input.parse()?
+        else {
+            return Err(err!(input, "unrecognized blade value"));
+        };
+        let blade: Blank = ident.to_string().parse()?;
+        Ok(Self {
+            attrs,
+            blade,
+            colon,
+            expr,
+        })
+    }
+}
+
+impl From<BladeValue> for syn::FieldValue {
+    fn from(value: BladeValue) -> Self {
+        let bl = value.blade;
+        Self {
+            attrs: value.attrs,
+            member: syn::Member::Named(format_ident!("{bl}")),
+            colon_token: Some(value.colon),
+            expr: value.expr,
+        }
+    }
+}
 
 impl Parse for Shape {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -75,10 +110,10 @@ impl Visit<'_> for ShapeGatherer {
         match segment.ident.to_string().as_str() {
             "Mv" => {
                 self.fam = Some(ShapeFamily::Shape(Shape(vec![])));
-                self.visit_path_segment(&segment);
+                self.visit_path_segment(segment);
             }
             "Powerset" => {
-                self.visit_path_segment(&segment);
+                self.visit_path_segment(segment);
                 match &self.fam {
                     Some(ShapeFamily::Shape(inner)) => {
                         self.fam = Some(ShapeFamily::Powerset(inner.clone()))
@@ -87,13 +122,14 @@ impl Visit<'_> for ShapeGatherer {
                     None => (),
                 }
             }
-            basis => match self.fam.as_mut() {
-                Some(ShapeFamily::Shape(Shape(inner))) => match basis.parse() {
-                    Ok(blunt) => inner.push(blunt),
-                    Err(e) => self.err = Some(e),
-                },
-                _ => (),
-            },
+            basis => {
+                if let Some(ShapeFamily::Shape(Shape(inner))) = self.fam.as_mut() {
+                    match basis.parse() {
+                        Ok(blunt) => inner.push(blunt),
+                        Err(e) => self.err = Some(e),
+                    }
+                }
+            }
         }
     }
 }
@@ -168,16 +204,193 @@ impl SquareMap {
     ) -> syn::Result<impl Iterator<Item = syn::Item>> {
         if item.mac.path.get_ident() == Some(&format_ident!("square")) {
             let SquareMacroBody { axis, square, .. } = item.mac.parse_body()?;
-            if self.0.insert(axis, square).is_some() {
+            if !self.insert(axis, square) {
                 return Err(err!(item, "duplicate squares defined"));
             }
-            if let Blade::Pos(Honed(frame)) = &mut self.1 {
-                frame.push(axis) // note: potentially violates sorted invariant, invariant is restored before use
-            } else {
-                unreachable!()
+        }
+        Ok(std::iter::empty()) // the consts need every square, see `expand_consts`
+    }
+    /// the metric and product table of the algebra as consts, for tooling that reads the module
+    pub fn expand_consts(&self) -> impl Iterator<Item = syn::Item> {
+        let dim = Literal::usize_unsuffixed(self.0.len());
+        let n = Literal::usize_unsuffixed(1 << self.0.len());
+        let squares = self
+            .signature()
+            .into_iter()
+            .map(|(_, sq)| Literal::i8_unsuffixed(i8::from(sq)));
+        let basis = self
+            .basis()
+            .into_iter()
+            .map(|canon| canon.to_string())
+            .collect_vec();
+        let variants = basis.iter().map(|name| format_ident!("{name}"));
+        let cayley = self.cayley().into_iter().map(|row| {
+            let row = row.into_iter().map(|(sign, i)| {
+                let (sign, i) = (Literal::i8_unsuffixed(sign), Literal::usize_unsuffixed(i));
+                quote!((#sign, #i))
+            });
+            quote!([#(#row),*])
+        });
+        let items: [syn::Item; 6] = [
+            parse_quote! {
+                /// number of basis vectors
+                pub const DIM: usize = #dim;
+            },
+            parse_quote! {
+                /// every basis blade, ordered by grade then axis
+                pub const BASIS: [&str; #n] = [#(#basis),*];
+            },
+            parse_quote! {
+                /// the square of each basis vector, ordered by axis
+                pub const SQUARES: [i8; #dim] = [#(#squares),*];
+            },
+            parse_quote! {
+                /// `BASIS[i] * BASIS[j] == CAYLEY[i][j].0 * BASIS[CAYLEY[i][j].1]`
+                pub const CAYLEY: [[(i8, usize); #n]; #n] = [#(#cayley),*];
+            },
+            parse_quote! {
+                /// a basis blade, `Basis::e02 as usize` indexes `BASIS`
+                #[allow(non_camel_case_types)]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                pub enum Basis {
+                    #(#variants),*
+                }
+            },
+            parse_quote! {
+                impl Basis {
+                    pub fn name(self) -> &'static str {
+                        BASIS[self as usize]
+                    }
+                }
+            },
+        ];
+        items.into_iter()
+    }
+    /// `parse_blade`, finding the `BASIS` index and orientation of a blade named in any axis order.
+    /// the axes are sorted at runtime rather than matching every permutation of every blade
+    pub fn expand_blade_parser() -> syn::Item {
+        parse_quote! {
+            /// the index into `BASIS` and the orientation of a blade named in any axis order,
+            /// `parse_blade("e20") == Some((i, -1))` when `BASIS[i] == "e02"`
+            pub fn parse_blade(name: &str) -> Option<(usize, i8)> {
+                if name == "scalar" {
+                    return Some((0, 1));
+                }
+                let mut axes = vec![];
+                let mut prefix = None;
+                for c in name.chars() {
+                    match c {
+                        'a'..='z' => prefix = Some(c),
+                        '0'..='9' | 'A'..='Z' => axes.push((prefix?, c)),
+                        _ => return None,
+                    }
+                }
+                // sort into axis order, each swap flips the orientation
+                let mut sign = 1;
+                for i in 1..axes.len() {
+                    let mut j = i;
+                    while j > 0 && axes[j - 1] > axes[j] {
+                        axes.swap(j - 1, j);
+                        sign = -sign;
+                        j -= 1;
+                    }
+                }
+                // the canonical name, writing each prefix once like `BASIS` does
+                let mut canon = String::new();
+                let mut last = None;
+                for axis in axes {
+                    if last == Some(axis) {
+                        return None;
+                    }
+                    if last.map(|(prefix, _)| prefix) != Some(axis.0) {
+                        canon.push(axis.0);
+                    }
+                    canon.push(axis.1);
+                    last = Some(axis);
+                }
+                let i = BASIS.iter().position(|blade| *blade == canon)?;
+                Some((i, sign))
             }
         }
-        Ok(std::iter::empty()) // currently no code is generated here, maybe a const in the future?
+    }
+    /// the declared squares, ordered by axis
+    pub fn signature(&self) -> Vec<(Axis, Squared)> {
+        self.0
+            .iter()
+            .map(|(axis, sq)| (*axis, sq.clone()))
+            .sorted()
+            .collect()
+    }
+    /// declare the square of an axis, the psuedoscalar is only valid after `hone_ps`
+    pub fn insert(&mut self, axis: Axis, square: Squared) -> bool {
+        if self.0.insert(axis, square).is_some() {
+            return false;
+        }
+        if let Blade::Pos(Honed(frame)) = &mut self.1 {
+            frame.push(axis) // note: potentially violates sorted invariant, invariant is restored before use
+        } else {
+            unreachable!()
+        }
+        true
+    }
+    /// restore the blade invariants for the psuedoscalar once every square is declared
+    pub fn hone_ps(&mut self) {
+        let Blade::Pos(Honed(frame)) = std::mem::take(&mut self.1) else {
+            unreachable!()
+        };
+        let ps: Blunt = Blank::Pos(frame).into();
+        self.1 = ps.hone(self);
+    }
+    /// the literal grades of `x.flip(1, 2)`
+    pub fn parse_grades<'e>(
+        &self,
+        args: impl IntoIterator<Item = &'e syn::Expr>,
+    ) -> syn::Result<Vec<usize>> {
+        args.into_iter().map(|arg| self.parse_grade(arg)).collect()
+    }
+    /// `a..=b`, `a..b` or open ended, as in `x.grade_range(1..=2)`
+    pub fn parse_grade_range(&self, expr: &syn::Expr) -> syn::Result<RangeInclusive<usize>> {
+        let syn::Expr::Range(range) = expr else {
+            return Err(err!(expr, "expected a range of grades"));
+        };
+        let start = match &range.start {
+            Some(start) => self.parse_grade(start)?,
+            None => 0,
+        };
+        let end = match (&range.end, range.limits) {
+            (None, _) => self.0.len(),
+            (Some(end), RangeLimits::Closed(_)) => self.parse_grade(end)?,
+            (Some(end), RangeLimits::HalfOpen(_)) => match self.parse_grade(end)?.checked_sub(1) {
+                Some(end) => end,
+                None => return Err(err!(expr, "empty range of grades")),
+            },
+        };
+        Ok(start..=end)
+    }
+    fn parse_grade(&self, expr: &syn::Expr) -> syn::Result<usize> {
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(n),
+            ..
+        }) = expr
+        else {
+            return Err(err!(expr, "expected a grade"));
+        };
+        match n.base10_parse()? {
+            grade if grade <= self.0.len() => Ok(grade),
+            _ => Err(err!(expr, format!("grades only go up to {}", self.0.len()))),
+        }
+    }
+}
+
+/// build the metric at runtime, later duplicates of an axis are ignored
+impl FromIterator<(Axis, Squared)> for SquareMap {
+    fn from_iter<T: IntoIterator<Item = (Axis, Squared)>>(iter: T) -> Self {
+        let mut squares = Self::default();
+        for (axis, square) in iter {
+            squares.insert(axis, square);
+        }
+        squares.hone_ps();
+        squares
     }
 }
 
@@ -203,7 +416,7 @@ impl Display for Canon {
 impl Display for Blank {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Zero => return f.write_str("Zero"),
+            Self::Zero => f.write_str("Zero"),
             Self::Pos(frame) => frame_fmt(frame, f),
             Self::Neg(frame) => {
                 f.write_char('N')?;
@@ -215,7 +428,7 @@ impl Display for Blank {
 impl Display for Blunt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Zero => return f.write_str("Zero"),
+            Self::Zero => f.write_str("Zero"),
             Self::Pos(Sorted(frame)) => frame_fmt(frame, f),
             Self::Neg(Sorted(frame)) => {
                 f.write_char('N')?;
@@ -227,7 +440,7 @@ impl Display for Blunt {
 impl Display for Blade {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Zero => return f.write_str("Zero"),
+            Self::Zero => f.write_str("Zero"),
             Self::Pos(Honed(frame)) => frame_fmt(frame, f),
             Self::Neg(Honed(frame)) => {
                 f.write_char('N')?;
@@ -0,0 +1,419 @@
+use super::{
+    Battery, Blade, Blank, Blunt, Canon, CanonMap, Honed, Mvect, Shape, ShapeFamily, ShapeMap,
+    SquareMap,
+};
+use crate::cas::CasExpr;
+use itertools::{Either, EitherOrBoth, Itertools, Powerset};
+use quote::{format_ident, quote};
+use std::{
+    collections::{BTreeMap, HashSet},
+    iter::FilterMap,
+    ops::Not,
+};
+use syn::parse_quote;
+
+impl ShapeMap {
+    pub fn shapes(&self, id: &syn::Ident) -> impl Iterator<Item = Shape> {
+        self.family(id).flatten()
+    }
+    /// every declared shape once, ordered by name
+    pub fn all_shapes(&self) -> Vec<Shape> {
+        let shapes = self.0.values().flatten().cloned().flatten();
+        shapes
+            .sorted_by_cached_key(|shape| shape.to_string())
+            .dedup_by(|a, b| a.to_string() == b.to_string())
+            .collect()
+    }
+    pub fn family(&self, id: &syn::Ident) -> impl Iterator<Item = ShapeFamily> {
+        match self.0.get(id) {
+            Some(family) => Either::Left(family.clone().into_iter()),
+            None => Either::Right(std::iter::empty()),
+        }
+    }
+    /// `PartialEq` and `ApproxEq` between every pair of shapes sharing a family,
+    /// skipping `PartialEq<Self>` for shapes that already derive it
+    pub fn expand_comparisons(
+        &self,
+        squares: &SquareMap,
+        derived: &HashSet<syn::Ident>,
+    ) -> syn::Result<Vec<syn::Item>> {
+        let mut items = vec![parse_quote! {
+            pub trait ApproxEq<Rhs = Self> {
+                fn approx_eq(&self, other: &Rhs, eps: Field) -> bool;
+            }
+        }];
+        let mut seen = HashSet::new();
+        for id in self.0.keys().sorted() {
+            let shapes = self.shapes(id).collect_vec();
+            for (lhs, rhs) in shapes.iter().cartesian_product(&shapes) {
+                let (lhs_id, rhs_id) = (format_ident!("{lhs}"), format_ident!("{rhs}"));
+                if !seen.insert((lhs_id.clone(), rhs_id.clone())) {
+                    continue;
+                }
+                let lhs_mv = lhs.clone().into_mv_cas(format_ident!("self"), squares);
+                let rhs_mv = rhs.clone().into_mv_cas(format_ident!("other"), squares);
+                if lhs != rhs || !derived.contains(&lhs_id) {
+                    let eq = lhs_mv.clone().try_into_eq_expr(rhs_mv.clone())?;
+                    items.push(parse_quote! {
+                        impl PartialEq<#rhs_id> for #lhs_id {
+                            fn eq(&self, other: &#rhs_id) -> bool {
+                                #eq
+                            }
+                        }
+                    });
+                }
+                let approx_eq = lhs_mv.try_into_approx_eq_expr(rhs_mv, &parse_quote!(eps))?;
+                items.push(parse_quote! {
+                    impl ApproxEq<#rhs_id> for #lhs_id {
+                        fn approx_eq(&self, other: &#rhs_id, eps: Field) -> bool {
+                            #approx_eq
+                        }
+                    }
+                });
+            }
+        }
+        Ok(items)
+    }
+    pub fn into_canon_map(self, squares: &SquareMap) -> CanonMap {
+        CanonMap(
+            self.0
+                .into_values()
+                .flatten()
+                .flatten()
+                .map(|shape| (shape.clone().into_battery(squares), shape))
+                .collect(),
+        )
+    }
+}
+impl Shape {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// `LEN` and conversions from and to `[Field; LEN]` in declared blade order,
+    /// `as_array` views need a `#[repr(C)]` struct and `pod` implements `bytemuck::Pod`
+    pub fn expand_arrays(&self, zero_copy: bool, pod: bool) -> Vec<syn::Item> {
+        let shape_id = format_ident!("{self}");
+        let len = self.0.len();
+        let blades = self.0.iter().map(|b| format_ident!("{b}")).collect_vec();
+        let indices = 0..len;
+        let views = zero_copy.then(|| {
+            quote! {
+                /// the fields in declared blade order, without copying
+                pub fn as_array(&self) -> &[Field; #len] {
+                    // SAFETY: `#[repr(C)]` with `LEN` fields of `Field`, so no padding
+                    unsafe { &*(self as *const Self as *const [Field; #len]) }
+                }
+                pub fn as_array_mut(&mut self) -> &mut [Field; #len] {
+                    // SAFETY: see `as_array`
+                    unsafe { &mut *(self as *mut Self as *mut [Field; #len]) }
+                }
+            }
+        });
+        let mut items = vec![
+            parse_quote! {
+                impl #shape_id {
+                    /// number of fields
+                    pub const LEN: usize = #len;
+                    #views
+                }
+            },
+            parse_quote! {
+                impl From<[Field; #len]> for #shape_id {
+                    #[allow(unused_variables)]
+                    fn from(values: [Field; #len]) -> Self {
+                        Self { #(#blades: values[#indices],)* }
+                    }
+                }
+            },
+            parse_quote! {
+                impl From<#shape_id> for [Field; #len] {
+                    #[allow(unused_variables)]
+                    fn from(mv: #shape_id) -> Self {
+                        [#(mv.#blades),*]
+                    }
+                }
+            },
+        ];
+        if pod {
+            // the impls below are only sound for `Pod` fields and a struct without padding,
+            // which an extra `#[repr(align(..))]` would add
+            items.push(parse_quote! {
+                const _: () = {
+                    fn field_is_pod<T: ::bytemuck::Pod>() {}
+                    let _ = field_is_pod::<Field>;
+                    assert!(
+                        ::std::mem::size_of::<#shape_id>() == #len * ::std::mem::size_of::<Field>(),
+                        "bytemuck shapes can't have padding"
+                    );
+                };
+            });
+            // SAFETY: `#[repr(C)]`, `Copy`, no padding and only `Pod` fields, checked above
+            items.push(parse_quote!(unsafe impl ::bytemuck::Zeroable for #shape_id {}));
+            items.push(parse_quote!(unsafe impl ::bytemuck::Pod for #shape_id {}));
+        }
+        items
+    }
+    /// `fmt_terms`, shared by the generated `Display` impls
+    ///     1.5e1 + 2e20 - 0.5e12
+    /// zeros are skipped unless formatted with `{:#}` and the precision applies to every value
+    pub fn expand_fmt_terms() -> syn::Item {
+        parse_quote! {
+            fn fmt_terms(terms: &[(&str, Field)], f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                let zero = Field::default();
+                let mut first = true;
+                for &(blade, value) in terms {
+                    if value == zero && !f.alternate() {
+                        continue;
+                    }
+                    let abs = if value < zero { -value } else { value };
+                    match (first, value < zero) {
+                        (true, true) => f.write_str("-")?,
+                        (true, false) => (),
+                        (false, true) => f.write_str(" - ")?,
+                        (false, false) => f.write_str(" + ")?,
+                    }
+                    match f.precision() {
+                        Some(precision) => write!(f, "{abs:.precision$}{blade}")?,
+                        None => write!(f, "{abs}{blade}")?,
+                    }
+                    first = false;
+                }
+                match first {
+                    true => f.write_str("0"),
+                    false => Ok(()),
+                }
+            }
+        }
+    }
+    /// `get`, the oriented component along a `Basis` blade, zero for blades outside the shape
+    pub fn expand_get(&self, squares: &SquareMap) -> syn::Item {
+        let shape_id = format_ident!("{self}");
+        let arms = self.0.iter().map(|blank| {
+            let field = format_ident!("{blank}");
+            match Blunt::from(blank.clone()).hone(squares) {
+                Blade::Pos(canon) => {
+                    let variant = format_ident!("{canon}");
+                    quote!(Basis::#variant => self.#field)
+                }
+                Blade::Neg(canon) => {
+                    let variant = format_ident!("{canon}");
+                    quote!(Basis::#variant => -self.#field)
+                }
+                Blade::Zero => unreachable!("shapes have no zero blades"),
+            }
+        });
+        parse_quote! {
+            impl #shape_id {
+                /// the component along `blade`, negated when the field has the opposite orientation
+                #[allow(unreachable_patterns)]
+                pub fn get(&self, blade: Basis) -> Field {
+                    match blade {
+                        #(#arms,)*
+                        _ => Field::default(),
+                    }
+                }
+            }
+        }
+    }
+    /// `Display` in algebraic notation through the `fmt_terms` of the module
+    pub fn expand_display(&self) -> syn::Item {
+        let shape_id = format_ident!("{self}");
+        let terms = self.0.iter().map(|blank| {
+            let field = format_ident!("{blank}");
+            let blade = match blank {
+                Blank::Pos(frame) if frame.is_empty() => String::new(),
+                blank => blank.to_string(),
+            };
+            quote!((#blade, self.#field))
+        });
+        parse_quote! {
+            impl ::std::fmt::Display for #shape_id {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    fmt_terms(&[#(#terms),*], f)
+                }
+            }
+        }
+    }
+    /// `Serialize` as a map keyed by blade name and `Deserialize` from any such map whose
+    /// extra blades are zero, using the `parse_blade` of the module
+    pub fn expand_serde(&self, squares: &SquareMap) -> Vec<syn::Item> {
+        let shape_id = format_ident!("{self}");
+        let len = self.0.len();
+        let names = self.0.iter().map(|b| b.to_string()).collect_vec();
+        let blades = self.0.iter().map(|b| format_ident!("{b}")).collect_vec();
+        let basis = squares.basis();
+        let arms = self.0.iter().enumerate().map(|(slot, blank)| {
+            let (canon, sign) = match Blunt::from(blank.clone()).hone(squares) {
+                Blade::Pos(canon) => (canon, 1i8),
+                Blade::Neg(canon) => (canon, -1),
+                Blade::Zero => unreachable!("shapes have no zero blades"),
+            };
+            let i = basis.iter().position(|b| *b == canon).unwrap();
+            quote!(#i => (#slot, #sign))
+        });
+        let slots = 0..len;
+        let expecting = format!("a map of the blades of {shape_id}");
+        vec![
+            parse_quote! {
+                impl ::serde::Serialize for #shape_id {
+                    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        use ::serde::ser::SerializeMap;
+                        let mut map = serializer.serialize_map(Some(#len))?;
+                        #(map.serialize_entry(#names, &self.#blades)?;)*
+                        map.end()
+                    }
+                }
+            },
+            parse_quote! {
+                impl<'de> ::serde::Deserialize<'de> for #shape_id {
+                    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                        struct Visitor;
+                        impl<'de> ::serde::de::Visitor<'de> for Visitor {
+                            type Value = #shape_id;
+                            fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                                f.write_str(#expecting)
+                            }
+                            fn visit_map<A: ::serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                                use ::serde::de::Error;
+                                const FIELDS: &[&str] = &[#(#names),*];
+                                let mut values: [Option<Field>; #len] = [None; #len];
+                                while let Some(key) = map.next_key::<String>()? {
+                                    let value: Field = map.next_value()?;
+                                    let Some((i, key_sign)) = parse_blade(&key) else {
+                                        return Err(A::Error::unknown_field(&key, FIELDS));
+                                    };
+                                    let (slot, sign) = match i {
+                                        #(#arms,)*
+                                        _ if value == Field::default() => continue,
+                                        _ => {
+                                            return Err(A::Error::custom(format!(
+                                                "`{key}` is {value} but {} has no such blade",
+                                                stringify!(#shape_id)
+                                            )))
+                                        }
+                                    };
+                                    let value = if key_sign * sign < 0 { -value } else { value };
+                                    if values[slot].replace(value).is_some() {
+                                        return Err(A::Error::custom(format!("duplicate blade `{key}`")));
+                                    }
+                                }
+                                Ok(#shape_id { #(#blades: values[#slots].unwrap_or_default(),)* })
+                            }
+                        }
+                        deserializer.deserialize_map(Visitor)
+                    }
+                }
+            },
+        ]
+    }
+    pub fn into_mv_cas(self, ident: syn::Ident, squares: &SquareMap) -> Mvect<'_, CasExpr> {
+        self.0
+            .into_iter()
+            .fold(Mvect(BTreeMap::new(), squares), |mv, blank| {
+                let value = CasExpr::var(format!("{ident}__{blank}"));
+                let blade = Blunt::hone(blank.into(), squares);
+                mv.add_blade_value(blade, value)
+            })
+    }
+    pub fn into_battery(self, squares: &SquareMap) -> Battery {
+        self.into_iter()
+            .map(|bl| match Blunt::from(bl).hone(squares) {
+                Blade::Zero => unreachable!(),
+                Blade::Pos(canon) | Blade::Neg(canon) => canon,
+            })
+            .collect()
+    }
+}
+impl ShapeFamily {
+    pub fn contains(&self, shape: &Shape) -> bool {
+        if shape.0.is_empty() {
+            return false;
+        }
+        match self {
+            Self::Shape(s) => s == shape,
+            Self::Powerset(ps) => {
+                let mut ps_iter = ps.0.iter();
+                for bl in &shape.0 {
+                    loop {
+                        match ps_iter.next() {
+                            Some(ps_bl) if ps_bl == bl => break,
+                            None => return false,
+                            _ => (),
+                        }
+                    }
+                }
+                true
+            }
+        }
+    }
+}
+// impl IntoIterator for ShapeMap {
+//     type IntoIter = Flatten<Flatten<hash_map::IntoValues<syn::Ident, Vec<ShapeFamily>>>>;
+//     type Item = Shape;
+//     fn into_iter(self) -> Self::IntoIter {
+//         self.0.into_values().flatten().flatten()
+//     }
+// }
+impl IntoIterator for ShapeFamily {
+    type IntoIter = Either<
+        std::iter::Once<Self::Item>,
+        FilterMap<Powerset<std::vec::IntoIter<Blank>>, fn(Vec<Blank>) -> Option<Shape>>,
+    >;
+    type Item = Shape;
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Self::Shape(shape) => Either::Left(std::iter::once(shape)),
+            Self::Powerset(shape) => Either::Right(
+                shape
+                    .into_iter()
+                    .powerset()
+                    .filter_map(|v| v.is_empty().not().then_some(Shape(v))),
+            ),
+        }
+    }
+}
+impl IntoIterator for Shape {
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type Item = Blank;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl FromIterator<Blank> for Shape {
+    fn from_iter<T: IntoIterator<Item = Blank>>(iter: T) -> Self {
+        Shape(iter.into_iter().collect())
+    }
+}
+impl Battery {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// nothing but the scalar, or zero
+    pub fn is_scalar(&self) -> bool {
+        self.0.iter().all(|canon| canon.0.is_empty())
+    }
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .all(|canon| other.0.binary_search(canon).is_ok())
+    }
+    pub fn union(&self, other: &Self) -> Self {
+        self.0.iter().chain(other.0.iter()).cloned().collect()
+    }
+    /// number of canonical blades present in only one of the two batteries
+    pub fn distance(&self, other: &Self) -> usize {
+        self.0
+            .iter()
+            .merge_join_by(other.0.iter(), Ord::cmp)
+            .filter(|both| !matches!(both, EitherOrBoth::Both(..)))
+            .count()
+    }
+}
+impl FromIterator<Canon> for Battery {
+    fn from_iter<T: IntoIterator<Item = Canon>>(iter: T) -> Self {
+        Honed(iter.into_iter().sorted().dedup().collect())
+    }
+}
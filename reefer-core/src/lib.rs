@@ -0,0 +1,42 @@
+//! the geometric algebra engine behind the `reefer` macros,
+//! a plain library so the same blade algebra is usable at runtime and in build scripts
+pub mod algebra;
+/// the runtime half of `meet` and `join`, compiled here for the evaluator
+/// and expanded as `mod blades` into every algebra
+pub mod blades {
+    pub type Field = f64;
+    include!("blades.rs");
+
+    /// the items above, using the `Field` of the algebra
+    pub fn expand() -> syn::Item {
+        let items = syn::parse_file(include_str!("blades.rs")).expect("blades.rs parses");
+        let items = items.items;
+        syn::parse_quote! {
+            #[allow(dead_code)]
+            mod blades {
+                use super::Field;
+                #(#items)*
+            }
+        }
+    }
+}
+pub mod cas;
+pub mod check;
+pub mod eval;
+pub mod geometry;
+pub mod shader;
+pub mod sort;
+pub mod traits;
+
+/// helper for building syn::Error errors
+#[macro_export]
+macro_rules! err {
+    ($expr:expr, $msg:expr) => {{
+        #[allow(unused_imports)]
+        use ::syn::spanned::Spanned;
+        ::syn::Error::new(($expr).span(), $msg)
+    }};
+    ($msg:expr) => {
+        ::syn::Error::new(::proc_macro2::Span::call_site(), $msg)
+    };
+}
@@ -0,0 +1,133 @@
+//! WGSL and GLSL rendering of shapes and reified values
+use crate::{cas::CasExpr, geometry::Shape};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Wgsl,
+    Glsl,
+}
+
+impl Lang {
+    pub fn ext(self) -> &'static str {
+        match self {
+            Lang::Wgsl => "wgsl",
+            Lang::Glsl => "glsl",
+        }
+    }
+    /// shaders always use 32 bit floats, whatever the `Field` of the module is
+    pub fn float(self) -> &'static str {
+        match self {
+            Lang::Wgsl => "f32",
+            Lang::Glsl => "float",
+        }
+    }
+    /// `1 / x` or zero, `x` is evaluated twice
+    pub(crate) fn recip_or_zero(self, x: &str) -> String {
+        match self {
+            Lang::Wgsl => format!("select(1.0 / {x}, 0.0, {x} == 0.0)"),
+            Lang::Glsl => format!("({x} == 0.0 ? 0.0 : 1.0 / {x})"),
+        }
+    }
+    pub(crate) fn inverse_sqrt(self) -> &'static str {
+        match self {
+            Lang::Wgsl => "inverseSqrt",
+            Lang::Glsl => "inversesqrt",
+        }
+    }
+    /// `self` is reserved in WGSL
+    pub fn ident(self, name: &str) -> String {
+        match name {
+            "self" => "self_".into(),
+            name => name.into(),
+        }
+    }
+    pub fn float_lit(self, value: f64) -> String {
+        match value < 0.0 {
+            true => format!("({value:?})"),
+            false => format!("{value:?}"),
+        }
+    }
+    pub fn struct_(self, shape: &Shape) -> String {
+        let fields = shape.clone().into_iter().map(|blank| match self {
+            Lang::Wgsl => format!("    {blank}: f32,\n"),
+            Lang::Glsl => format!("    float {blank};\n"),
+        });
+        match self {
+            Lang::Wgsl => format!("struct {shape} {{\n{}}}\n", fields.format("")),
+            Lang::Glsl => format!("struct {shape} {{\n{}}};\n", fields.format("")),
+        }
+    }
+    pub fn const_(self, name: &str, value: &CasExpr) -> String {
+        let value = value.to_shader(self);
+        match self {
+            Lang::Wgsl => format!("const {name}: f32 = {value};\n"),
+            Lang::Glsl => format!("const float {name} = {value};\n"),
+        }
+    }
+    /// a fn returning a single expression, `params` are `(name, type)` pairs
+    pub fn fn_(self, name: &str, params: &[(String, String)], ret: &str, value: &str) -> String {
+        let params = params.iter().map(|(param, ty)| match self {
+            Lang::Wgsl => format!("{}: {ty}", self.ident(param)),
+            Lang::Glsl => format!("{ty} {}", self.ident(param)),
+        });
+        match self {
+            Lang::Wgsl => format!(
+                "fn {name}({}) -> {ret} {{\n    return {value};\n}}\n",
+                params.format(", ")
+            ),
+            Lang::Glsl => format!(
+                "{ret} {name}({}) {{\n    return {value};\n}}\n",
+                params.format(", ")
+            ),
+        }
+    }
+    /// constructor call for a shape
+    pub fn construct(self, shape: &Shape, values: &[CasExpr]) -> String {
+        let values = values.iter().map(|value| value.to_shader(self));
+        format!("{shape}({})", values.format(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{RecipOrZero, Squareroot};
+
+    #[test]
+    fn test_expr() {
+        let x = CasExpr::var("self__e1");
+        let y = CasExpr::var("line__e0");
+        let expr = (x.clone() * x.clone() - y.clone() / CasExpr::int(2)).simplify();
+        let wgsl = expr.to_shader(Lang::Wgsl);
+        assert!(wgsl.contains("self_.e1 * self_.e1"), "{wgsl}");
+        assert!(wgsl.contains("line.e0"), "{wgsl}");
+        assert!(!wgsl.contains("pow"), "{wgsl}");
+        let norm = (x.clone() * x).sqrt();
+        assert_eq!(norm.to_shader(Lang::Glsl), "sqrt((self_.e1 * self_.e1))");
+        let recip = (CasExpr::int(1) / y.sqrt()).simplify();
+        assert_eq!(recip.to_shader(Lang::Wgsl), "inverseSqrt(line.e0)");
+        let guarded = CasExpr::var("line__e0").recip_or_zero();
+        assert_eq!(
+            guarded.to_shader(Lang::Wgsl),
+            "select(1.0 / line.e0, 0.0, line.e0 == 0.0)"
+        );
+        assert_eq!(
+            guarded.to_shader(Lang::Glsl),
+            "(line.e0 == 0.0 ? 0.0 : 1.0 / line.e0)"
+        );
+    }
+
+    #[test]
+    fn test_struct() {
+        let shape: Shape = ["e01", "e20"].iter().map(|b| b.parse().unwrap()).collect();
+        assert_eq!(
+            Lang::Wgsl.struct_(&shape),
+            "struct Mv_e01_e20 {\n    e01: f32,\n    e20: f32,\n}\n"
+        );
+        assert_eq!(
+            Lang::Glsl.struct_(&shape),
+            "struct Mv_e01_e20 {\n    float e01;\n    float e20;\n};\n"
+        );
+    }
+}
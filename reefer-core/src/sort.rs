@@ -155,14 +155,14 @@ mod tests {
         let arr: Vec<usize> = vec![];
         let (arr, inversions) = guarded_parity_sort(arr);
         assert!(arr.is_empty());
-        assert_eq!(inversions, false);
+        assert!(!inversions);
     }
 
     #[test]
     fn test_single_element() {
         let arr = [5].to_vec();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, false);
+        assert!(!inversions);
         assert_eq!(arr, [5]);
     }
 
@@ -170,7 +170,7 @@ mod tests {
     fn test_two_element_sorted() {
         let arr = ["1", "2"].to_vec();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, false);
+        assert!(!inversions);
         assert_eq!(arr, ["1", "2"]);
     }
 
@@ -178,7 +178,7 @@ mod tests {
     fn test_two_element() {
         let arr = [5, 1].to_vec();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, true);
+        assert!(inversions);
         assert_eq!(arr, [1, 5]);
     }
 
@@ -186,7 +186,7 @@ mod tests {
     fn test_sorted_array() {
         let arr = [1, 2, 3, 4, 5].to_vec();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, false);
+        assert!(!inversions);
         assert_eq!(arr, [1, 2, 3, 4, 5]);
     }
 
@@ -194,7 +194,7 @@ mod tests {
     fn test_reverse_sorted_array() {
         let arr = [5, 4, 3, 2, 1].to_vec();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, false);
+        assert!(!inversions);
         assert_eq!(arr, [1, 2, 3, 4, 5]);
     }
 
@@ -202,7 +202,7 @@ mod tests {
     fn test_partially_sorted_array() {
         let arr = [1, 3, 2, 5].to_vec();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, true);
+        assert!(inversions);
         assert_eq!(arr, [1, 2, 3, 5]);
     }
 
@@ -210,7 +210,7 @@ mod tests {
     fn test_example_array() {
         let arr = [5, 3, 2, 4, 1].to_vec();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, false);
+        assert!(!inversions);
         assert_eq!(arr, [1, 2, 3, 4, 5]);
     }
 
@@ -218,7 +218,7 @@ mod tests {
     fn test_duplicate_elements() {
         let arr = [4, 2, 3, 1, 2].to_vec();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, true);
+        assert!(inversions);
         assert_eq!(arr, [1, 2, 2, 3, 4]);
     }
 
@@ -226,7 +226,7 @@ mod tests {
     fn test_large_array() {
         let arr: Vec<i32> = (0..1000).rev().collect();
         let (arr, inversions) = guarded_parity_sort(arr);
-        assert_eq!(inversions, false);
+        assert!(!inversions);
         assert!(arr.is_sorted());
     }
 }
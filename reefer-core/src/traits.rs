@@ -0,0 +1,8 @@
+pub trait Squareroot {
+    type Output;
+    fn sqrt(self) -> Self::Output;
+}
+/// `1 / x`, or zero where `x` is, for divisions whose result vanishes along with the divisor
+pub trait RecipOrZero {
+    fn recip_or_zero(self) -> Self;
+}
@@ -0,0 +1,62 @@
+use super::reifier::ShapeCast;
+use std::collections::HashMap;
+use syn::parse::Parse;
+
+/// a reified fn that other reified bodies in the same module can call
+#[derive(Debug, Clone)]
+pub struct Callee {
+    pub binds: Vec<ShapeCast>,
+    /// the type of each argument, `self` included
+    pub inputs: Vec<(syn::Ident, syn::Type)>,
+    pub fn_: syn::ImplItemFn,
+}
+
+/// reified fns by name
+#[derive(Debug, Clone, Default)]
+pub struct CalleeMap(HashMap<syn::Ident, Vec<Callee>>);
+
+impl CalleeMap {
+    pub fn get(&self, id: &syn::Ident) -> &[Callee] {
+        self.0.get(id).map_or(&[], Vec::as_slice)
+    }
+    pub fn insert_impl(&mut self, impl_: &syn::ItemImpl) -> syn::Result<()> {
+        let binds: Vec<ShapeCast> = impl_
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("reify"))
+            .map(|attr| attr.parse_args_with(ShapeCast::parse))
+            .collect::<syn::Result<_>>()?;
+        if binds.is_empty() {
+            return Ok(());
+        }
+        for item in &impl_.items {
+            let syn::ImplItem::Fn(fn_) = item else {
+                continue;
+            };
+            let inputs = fn_
+                .sig
+                .inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::FnArg::Receiver(_) => Some((
+                        syn::Ident::new("self", fn_.sig.ident.span()),
+                        impl_.self_ty.as_ref().clone(),
+                    )),
+                    syn::FnArg::Typed(pat_ty) => match pat_ty.pat.as_ref() {
+                        syn::Pat::Ident(pat) => Some((pat.ident.clone(), *pat_ty.ty.clone())),
+                        _ => None,
+                    },
+                })
+                .collect();
+            self.0
+                .entry(fn_.sig.ident.clone())
+                .or_default()
+                .push(Callee {
+                    binds: binds.clone(),
+                    inputs,
+                    fn_: fn_.clone(),
+                });
+        }
+        Ok(())
+    }
+}
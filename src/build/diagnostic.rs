@@ -0,0 +1,93 @@
+use itertools::Itertools;
+use proc_macro2::Span;
+use quote::ToTokens;
+use reefer_core::geometry::{Battery, Shape, ShapeMap, SquareMap};
+use std::fmt::Display;
+
+/// how many declared shapes to suggest for a dropped variant
+const MAX_CLOSEST: usize = 3;
+
+/// a reify variant whose computed shape is outside of its `impl Shape` bounds
+#[derive(Debug, Clone)]
+pub struct Dropped {
+    span: Span,
+    inputs: Vec<(syn::Type, Shape)>,
+    battery: Battery,
+    bounds: Vec<syn::Ident>,
+    closest: Vec<Shape>,
+}
+
+impl Dropped {
+    pub fn new(
+        span: Span,
+        inputs: &[(syn::Type, Shape)],
+        shape: Shape,
+        bounds: Vec<syn::Ident>,
+        shapes: &ShapeMap,
+        squares: &SquareMap,
+    ) -> Self {
+        let battery = shape.into_battery(squares);
+        let closest = bounds
+            .iter()
+            .flat_map(|id| shapes.shapes(id))
+            .map(|shape| {
+                (
+                    shape.clone().into_battery(squares).distance(&battery),
+                    shape,
+                )
+            })
+            .sorted_by_key(|(dist, _)| *dist)
+            .map(|(_, shape)| shape)
+            .dedup()
+            .take(MAX_CLOSEST)
+            .collect();
+        Self {
+            span,
+            inputs: inputs.to_vec(),
+            battery,
+            bounds,
+            closest,
+        }
+    }
+    /// stable rust has no way to emit a warning from a proc macro, so piggyback on `deprecated`
+    pub fn into_warning(self) -> syn::Item {
+        let note = self.to_string();
+        let ident = syn::Ident::new("reify_variant_dropped", self.span);
+        syn::parse_quote! {
+            const _: () = {
+                #[deprecated(note = #note)]
+                #[allow(non_upper_case_globals)]
+                const #ident: () = ();
+                #ident
+            };
+        }
+    }
+    pub fn into_error(self) -> syn::Error {
+        syn::Error::new(self.span, self.to_string())
+    }
+}
+
+impl Display for Dropped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|(ty, shape)| format!("{} = {shape}", ty.to_token_stream()))
+            .join(", ");
+        let bounds = self.bounds.iter().join(" + ");
+        f.write_str("dropped reify variant ")?;
+        f.write_str(&inputs)?;
+        match self.battery.is_empty() {
+            true => write!(f, ": result is zero, not `impl {bounds}`")?,
+            false => write!(
+                f,
+                ": computed battery {} is not `impl {bounds}`",
+                self.battery
+            )?,
+        }
+        match self.closest.is_empty() {
+            true => write!(f, ", no {bounds} shapes are declared"),
+            false => write!(f, ", closest shapes: {}", self.closest.iter().join(", ")),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use quote::{format_ident, quote};
+use reefer_core::{err, geometry::ShapeMap};
+use syn::parse_quote;
+
+use super::{
+    shader::write_dump,
+    variant::{Operand, Variant},
+};
+
+/// the C type of `type Field`
+fn c_float(field: &syn::ItemType) -> syn::Result<&'static str> {
+    match &*field.ty {
+        syn::Type::Path(path) if path.path.is_ident("f32") => Ok("float"),
+        syn::Type::Path(path) if path.path.is_ident("f64") => Ok("double"),
+        ty => Err(err!(ty, "ffi needs `Field` to be `f32` or `f64`")),
+    }
+}
+
+/// `self` can't name the arg of a free fn
+fn param_ident(input: &syn::Ident) -> syn::Ident {
+    match input == "self" {
+        true => format_ident!("self_"),
+        false => input.clone(),
+    }
+}
+
+/// `#[no_mangle] extern "C"` wrappers for every reified variant, and a C header declaring them
+pub fn ffi_items(
+    mod_ident: &syn::Ident,
+    shapes: &ShapeMap,
+    field: Option<&syn::ItemType>,
+    variants: &[Variant],
+) -> syn::Result<Vec<syn::Item>> {
+    let Some(field) = field else {
+        return Err(err!(mod_ident, "ffi needs a `type Field` in the module"));
+    };
+    let float = c_float(field)?;
+    // the C type and the rust one. C has no zero sized structs, and bools, matrices, tuples
+    // and arrays are left to the rust api
+    let c_ty = |operand: &Operand| match operand {
+        Operand::Shape(shape) if shape.is_empty() => None,
+        Operand::Shape(shape) => Some((format!("{mod_ident}_{shape}"), format_ident!("{shape}"))),
+        Operand::Field => Some((float.to_string(), format_ident!("Field"))),
+        _ => None,
+    };
+    let guard = format!("REEFER_{}_H", mod_ident.to_string().to_uppercase());
+    let mut header = format!(
+        "// generated by reefer from `mod {mod_ident}`, do not edit\n#ifndef {guard}\n#define {guard}\n\n#ifdef __cplusplus\nextern \"C\" {{\n#endif\n"
+    );
+    let shapes = shapes.all_shapes().into_iter().filter(|s| !s.is_empty());
+    for shape in shapes {
+        let fields = shape
+            .clone()
+            .into_iter()
+            .map(|blank| format!("    {float} {blank};\n"));
+        let name = format!("{mod_ident}_{shape}");
+        header += &format!(
+            "\ntypedef struct {name} {{\n{}}} {name};\n",
+            fields.format("")
+        );
+    }
+    let mut items = vec![];
+    let mut names = HashSet::new();
+    for variant in variants {
+        let Some(path) = &variant.path else {
+            continue;
+        };
+        let Some(params) = variant
+            .inputs
+            .iter()
+            .map(|input| Some((param_ident(&input.ident), c_ty(&input.operand)?)))
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        let Some((ret, ret_ty)) = c_ty(&variant.output) else {
+            continue;
+        };
+        let name = format!("{mod_ident}_{}", variant.qualified_name()).to_lowercase();
+        if !names.insert(name.clone()) {
+            let msg = format!("another reified fn already exports as `{name}`, rename one of them");
+            return Err(err!(variant.ident, msg));
+        }
+        let ident = format_ident!("{name}");
+        let c_params = params
+            .iter()
+            .map(|(param, (ty, _))| format!("{ty} {param}"));
+        header += &format!("\n{ret} {ident}({});\n", c_params.format(", "));
+        let (args, tys): (Vec<_>, Vec<_>) = variant
+            .inputs
+            .iter()
+            .zip(&params)
+            .map(|(input, (param, (_, ty)))| {
+                let reference = input.by_ref.then(|| quote!(&));
+                (quote!(#reference #param), quote!(#param: #ty))
+            })
+            .unzip();
+        items.push(parse_quote! {
+            #[unsafe(no_mangle)]
+            pub extern "C" fn #ident(#(#tys),*) -> #ret_ty {
+                #path(#(#args),*)
+            }
+        });
+    }
+    header += &format!("\n#ifdef __cplusplus\n}}\n#endif\n\n#endif // {guard}\n");
+    write_dump(mod_ident, "h", &header);
+    items.push(parse_quote! {
+        /// C declarations of the shapes and `extern "C"` fns of this module
+        pub const C_HEADER: &str = #header;
+    });
+    Ok(items)
+}
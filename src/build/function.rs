@@ -0,0 +1,128 @@
+use itertools::Itertools;
+use quote::format_ident;
+use reefer_core::err;
+use syn::parse_quote;
+
+/// a reified free function, desugared into a trait implemented for the type of its first argument
+/// and a generic wrapper so call sites keep the plain `f(a, b)` syntax
+///     #[reify(Line as L)] #[reify(Point as P)] fn project(l: L, p: P) -> impl Point { .. }
+/// becomes
+///     trait Project<T1> { type Output; fn project(l: Self, p: T1) -> Self::Output; }
+///     #[reify(Line as L)] #[reify(Point as P)]
+///     impl Project<P> for L { type Output = impl Point; fn project(l: L, p: P) -> Self::Output { .. } }
+///     fn project<T0: Project<T1>, T1>(l: T0, p: T1) -> <T0 as Project<T1>>::Output { .. }
+pub struct ReifiedFn {
+    pub trait_: syn::ItemTrait,
+    pub impl_: syn::ItemImpl,
+    pub wrapper: syn::ItemFn,
+}
+
+pub fn is_reify_attr(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("reify") || attr.path().is_ident("verbose")
+}
+
+impl TryFrom<syn::ItemFn> for ReifiedFn {
+    type Error = syn::Error;
+    fn try_from(fn_: syn::ItemFn) -> syn::Result<Self> {
+        let syn::ItemFn {
+            attrs,
+            vis,
+            sig,
+            block,
+        } = fn_;
+        if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+            return Err(err!(sig.generics, "reified functions can't be generic"));
+        }
+        let (reify_attrs, attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(is_reify_attr);
+        // split the arguments into (ident, type) pairs
+        let args: Vec<(syn::Ident, syn::Type)> = sig
+            .inputs
+            .iter()
+            .map(|arg| match arg {
+                syn::FnArg::Typed(syn::PatType { pat, ty, .. }) => match pat.as_ref() {
+                    syn::Pat::Ident(pat) => Ok((pat.ident.clone(), ty.as_ref().clone())),
+                    pat => Err(err!(pat, "reified function arguments must be identifiers")),
+                },
+                syn::FnArg::Receiver(rec) => Err(err!(rec, "free functions can't take self")),
+            })
+            .try_collect()?;
+        let Some(((_, self_ty), other_args)) = args.split_first() else {
+            return Err(err!(sig, "reified functions need at least one argument"));
+        };
+        let ident = sig.ident.clone();
+        let trait_id = format_ident!(
+            "{}",
+            upper_camel_case(&ident.to_string()),
+            span = ident.span()
+        );
+        let output: syn::Type = match &sig.output {
+            syn::ReturnType::Default => parse_quote!(()),
+            syn::ReturnType::Type(_, ty) => ty.as_ref().clone(),
+        };
+        // generic stand-ins for the argument types
+        let generics = (0..args.len()).map(|n| format_ident!("T{n}")).collect_vec();
+        let (self_generic, other_generics) = generics.split_first().unwrap();
+        let arg_ids = args.iter().map(|(id, _)| id).collect_vec();
+        let other_ids = other_args.iter().map(|(id, _)| id).collect_vec();
+        let other_tys = other_args.iter().map(|(_, ty)| ty).collect_vec();
+        let self_id = arg_ids[0];
+
+        // other attrs like `#[inline]` or lint levels only make sense on the bodies
+        let docs = attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+        let mut trait_sig = sig.clone();
+        trait_sig.inputs = parse_quote!(#self_id: Self, #(#other_ids: #other_generics),*);
+        trait_sig.output = parse_quote!(-> Self::Output);
+        let trait_: syn::ItemTrait = parse_quote! {
+            #vis trait #trait_id<#(#other_generics),*> {
+                type Output;
+                #(#docs)*
+                #trait_sig;
+            }
+        };
+
+        let mut impl_sig = sig.clone();
+        impl_sig.output = parse_quote!(-> Self::Output);
+        let impl_: syn::ItemImpl = parse_quote! {
+            #(#reify_attrs)*
+            impl #trait_id<#(#other_tys),*> for #self_ty {
+                type Output = #output;
+                #(#attrs)*
+                #impl_sig #block
+            }
+        };
+
+        let mut wrapper_sig = sig;
+        wrapper_sig.generics =
+            parse_quote!(<#self_generic: #trait_id<#(#other_generics),*>, #(#other_generics),*>);
+        wrapper_sig.inputs = parse_quote!(#(#arg_ids: #generics),*);
+        wrapper_sig.output =
+            parse_quote!(-> <#self_generic as #trait_id<#(#other_generics),*>>::Output);
+        let wrapper: syn::ItemFn = parse_quote! {
+            #(#attrs)*
+            #vis #wrapper_sig {
+                <#self_generic as #trait_id<#(#other_generics),*>>::#ident(#(#arg_ids),*)
+            }
+        };
+
+        Ok(Self {
+            trait_,
+            impl_,
+            wrapper,
+        })
+    }
+}
+
+/// snake_case -> UpperCamelCase
+fn upper_camel_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
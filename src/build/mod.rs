@@ -1,18 +1,37 @@
-use crate::geometry::{Blank, Shape};
 use quote::format_ident;
-use syn::{
-    punctuated::Punctuated,
-    token::{Colon, Comma},
+use reefer_core::{
+    geometry::{BladeValue, Shape},
+    shader::Lang,
 };
+use syn::{punctuated::Punctuated, token::Comma};
 
+mod callee;
+mod diagnostic;
+mod ffi;
+mod function;
 mod parse;
 mod reifier;
+mod selftest;
+mod shader;
+mod variant;
 
-pub struct BladeValue {
-    pub attrs: Vec<syn::Attribute>,
-    pub blade: Blank,
-    pub colon: Colon,
-    pub expr: syn::Expr,
+/// module level options, `#[reefer::algebraic(selftest, wgsl, ffi)]`
+#[derive(Debug, Default)]
+pub struct Options {
+    /// generate tests checking each reified fn against the numeric evaluator
+    pub selftest: bool,
+    /// shader languages to emit the shapes and reified fns in
+    pub shaders: Vec<Lang>,
+    /// `#[repr(C)]` on every shape struct
+    pub repr_c: bool,
+    /// `extern "C"` wrappers for the reified fns and a C header, implies `repr_c`
+    pub ffi: bool,
+    /// `bytemuck::Pod` and `Zeroable` for every shape struct, which are made `#[repr(C)]` and `Copy`
+    pub bytemuck: bool,
+    /// blade keyed `serde::Serialize` and `Deserialize` for every shape struct
+    pub serde: bool,
+    /// leave out the `PartialEq` and `ApproxEq` impls between every pair of shapes sharing a family
+    pub no_comparisons: bool,
 }
 
 pub fn mv_ty_path(shape: Shape) -> syn::Result<syn::Path> {
@@ -31,14 +50,11 @@ pub fn mv(values: Punctuated<BladeValue, Comma>) -> syn::Result<syn::ExprStruct>
     })
 }
 
-pub fn algebraic(
-    _attrs: syn::parse::Nothing, // no attrs so expect nothing
-    mut mod_: syn::ItemMod,
-) -> syn::Result<syn::ItemMod> {
+pub fn algebraic(options: Options, mut mod_: syn::ItemMod) -> syn::Result<syn::ItemMod> {
     // expand and collect the shape! and square! macros
     let spec = reifier::BuildSpec::try_from(&mut mod_)?;
     // reify shape impls
-    spec.reify_mod(&mut mod_)?;
+    spec.reify_mod(&mut mod_, &options)?;
     Ok(mod_)
 }
 
@@ -48,8 +64,8 @@ mod tests {
 
     #[test]
     fn test_algebraic() -> syn::Result<()> {
-        let _mod_ = algebraic(
-            syn::parse::Nothing,
+        let mod_ = algebraic(
+            Options::default(),
             syn::parse_quote! {
                 mod pga2d {
                     use std::ops::{BitAnd, BitOr, BitXor};
@@ -145,7 +161,7 @@ mod tests {
                     pub trait Normalize {
                         type Output;
                         fn normalized(self) -> Self::Output;
-                        fn norm(self) -> Field;
+                        fn length(self) -> Field;
                         fn ideal_norm(self) -> Field;
                     }
                     #[reify(Line as L)]
@@ -154,7 +170,7 @@ mod tests {
                         fn normalized(self) -> Self::Output {
                             self.normed()
                         }
-                        fn norm(self) -> Field {
+                        fn length(self) -> Field {
                             self.norm().scalar
                         }
                         fn ideal_norm(self) -> Field {
@@ -182,6 +198,335 @@ mod tests {
             },
         )?;
         // println!("{mod_:?}");
+        // nothing meets or joins at runtime
+        let (_, items) = mod_.content.unwrap();
+        let is_blades = |item: &syn::Item| matches!(item, syn::Item::Mod(m) if m.ident == "blades");
+        assert!(!items.iter().any(is_blades));
+        // lints are only allowed on the reified fns, not the rest of the impl
+        let allows = |attrs: &[syn::Attribute]| attrs.iter().any(|a| a.path().is_ident("allow"));
+        for item in &items {
+            let syn::Item::Impl(impl_) = item else {
+                continue;
+            };
+            assert!(!allows(&impl_.attrs));
+            let Some((_, trait_, _)) = &impl_.trait_ else {
+                continue;
+            };
+            let expected = match trait_.segments[0].ident.to_string().as_str() {
+                "Projection" => true,
+                "Pow" => false,
+                _ => continue,
+            };
+            for impl_item in &impl_.items {
+                if let syn::ImplItem::Fn(fn_) = impl_item {
+                    assert_eq!(allows(&fn_.attrs), expected);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(syn::parse_str::<Options>("selftest").unwrap().selftest);
+        assert!(!syn::parse_str::<Options>("").unwrap().selftest);
+        let options = syn::parse_str::<Options>("wgsl, glsl, wgsl").unwrap();
+        assert_eq!(options.shaders, [Lang::Wgsl, Lang::Glsl]);
+        let options = syn::parse_str::<Options>("ffi").unwrap();
+        assert!(options.ffi && options.repr_c);
+        assert!(!syn::parse_str::<Options>("repr_c").unwrap().ffi);
+        assert!(syn::parse_str::<Options>("bytemuck").unwrap().bytemuck);
+        assert!(syn::parse_str::<Options>("serde").unwrap().serde);
+        assert!(!syn::parse_str::<Options>("").unwrap().no_comparisons);
+        let options = syn::parse_str::<Options>("no_comparisons").unwrap();
+        assert!(options.no_comparisons);
+        let err = syn::parse_str::<Options>("selftest, fast").unwrap_err();
+        assert_eq!(err.to_string(), "unknown option `fast`");
+    }
+
+    #[test]
+    fn test_no_comparisons() -> syn::Result<()> {
+        let has_approx_eq = |options: &str| -> syn::Result<bool> {
+            let mod_ = algebraic(
+                syn::parse_str(options)?,
+                syn::parse_quote! {
+                    mod vga2d {
+                        type Field = f64;
+
+                        square!(e1, 1);
+                        square!(e2, 1);
+
+                        shape!(Vector, Mv<e1>, Mv<e1, e2>);
+                    }
+                },
+            )?;
+            let (_, items) = mod_.content.unwrap();
+            Ok(items
+                .iter()
+                .any(|item| matches!(item, syn::Item::Trait(t) if t.ident == "ApproxEq")))
+        };
+        assert!(has_approx_eq("")?);
+        assert!(!has_approx_eq("no_comparisons")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ffi_collision() {
+        let err = algebraic(
+            syn::parse_str("ffi").unwrap(),
+            syn::parse_quote! {
+                mod pga2d {
+                    type Field = f32;
+
+                    square!(e0, 0);
+                    square!(e1, 1);
+                    square!(e2, 1);
+
+                    shape!(Line, Mv<e1, e2, e0>);
+
+                    mod a {
+                        pub trait Scale {
+                            fn scaled(self, k: super::Field) -> Self;
+                        }
+                    }
+                    mod b {
+                        pub trait Scale {
+                            fn scaled(self, k: super::Field) -> Self;
+                        }
+                    }
+
+                    #[reify(Line as L)]
+                    impl a::Scale for L {
+                        fn scaled(self, k: Field) -> Self {
+                            self * k
+                        }
+                    }
+                    #[reify(Line as L)]
+                    impl b::Scale for L {
+                        fn scaled(self, k: Field) -> Self {
+                            self * k
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "another reified fn already exports as `pga2d_scale_scaled_mv_e1_e2_e0_field`, \
+             rename one of them"
+        );
+    }
+
+    #[test]
+    fn test_skipped_selftest() -> syn::Result<()> {
+        let mod_ = algebraic(
+            syn::parse_str("selftest")?,
+            syn::parse_quote! {
+                mod vga2d {
+                    type Field = f64;
+
+                    square!(e1, 1);
+                    square!(e2, 1);
+
+                    shape!(Vector, Mv<e1, e2>);
+
+                    #[reify(Vector as V)]
+                    impl V {
+                        pub fn flipped(self) -> impl Vector {
+                            -self
+                        }
+                        pub fn unflipped(self) -> impl Vector {
+                            self.flipped().flipped()
+                        }
+                    }
+                }
+            },
+        )?;
+        let (_, items) = mod_.content.unwrap();
+        let Some(syn::Item::Mod(selftest)) = items.last() else {
+            panic!("no selftest module")
+        };
+        let (_, tests) = selftest.content.as_ref().unwrap();
+        let ignored = tests
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Fn(fn_) => fn_.attrs.iter().find(|a| a.path().is_ident("ignore")),
+                _ => None,
+            })
+            .map(
+                |attr| match &attr.meta.require_name_value().unwrap().value {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(reason),
+                        ..
+                    }) => reason.value(),
+                    _ => panic!("the reason should be a string"),
+                },
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ignored,
+            [
+                "unflipped(Mv_e1_e2) isn't checked, the evaluator can't run it: \
+              cannot evaluate method `flipped`"
+            ]
+        );
         Ok(())
     }
+
+    #[test]
+    fn test_nested_shadowing() -> syn::Result<()> {
+        // each shadowed call reifies its args once, so nesting stays linear
+        let calls = std::iter::repeat_n(".neg()", 24).collect::<String>();
+        let body: syn::Expr = syn::parse_str(&format!("self{calls}.simplify()"))?;
+        let mod_ = algebraic(
+            Options::default(),
+            syn::parse_quote! {
+                mod vga2d {
+                    type Field = f64;
+
+                    square!(e1, 1);
+                    square!(e2, 1);
+
+                    shape!(Vector, Mv<e1, e2>);
+
+                    #[reify(Vector as V)]
+                    impl V {
+                        pub fn neg(self) -> impl Vector {
+                            -self
+                        }
+                        pub fn unchanged(self) -> impl Vector {
+                            #body
+                        }
+                    }
+                }
+            },
+        )?;
+        let (_, items) = mod_.content.unwrap();
+        let unchanged = items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Impl(impl_) => impl_.items.iter().find_map(|item| match item {
+                    syn::ImplItem::Fn(fn_) if fn_.sig.ident == "unchanged" => Some(fn_),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            quote::ToTokens::to_token_stream(&unchanged.block).to_string(),
+            "{ Mv_e1_e2 { e1 : self . e1 , e2 : self . e2 } }"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_conflicting_return_shapes() {
+        let err = algebraic(
+            Options::default(),
+            syn::parse_quote! {
+                mod vga2d {
+                    type Field = f64;
+
+                    square!(e1, 1);
+                    square!(e2, 1);
+
+                    shape!(Vector, Mv<e1, e2>);
+                    shape!(Even, Mv<scalar, e12>);
+
+                    pub trait Both {
+                        type Output;
+                        fn same(self) -> Self::Output;
+                        fn squared(self) -> Self::Output;
+                    }
+                    #[reify(Vector as V)]
+                    impl Both for V {
+                        type Output = impl Vector;
+                        fn same(self) -> Self::Output {
+                            self
+                        }
+                        fn squared(self) -> Self::Output {
+                            self * self
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting shapes for this type, Mv_scalar_e12 and Mv_e1_e2"
+        );
+    }
+
+    #[test]
+    fn test_inline_mismatch() {
+        let err = algebraic(
+            Options::default(),
+            syn::parse_quote! {
+                mod vga2d {
+                    type Field = f64;
+
+                    square!(e1, 1);
+                    square!(e2, 1);
+
+                    shape!(Vector, Mv<e1, e2>);
+                    shape!(Even, Mv<scalar, e12>);
+
+                    #[reify(Vector as V)]
+                    pub fn scaled(v: V, t: Field) -> impl Vector {
+                        v * t
+                    }
+                    #[reify(Even as R)]
+                    pub fn twice(r: R) -> impl Even {
+                        scaled(r, 2.0)
+                    }
+                }
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no reified `scaled` accepts (Mv_scalar_e12, Mv_scalar), \
+             the variants are scaled(v: Vector, t: Field) with Vector one of Mv_e1_e2"
+        );
+    }
+
+    #[test]
+    fn test_dropped_variants() {
+        let err = algebraic(
+            Options::default(),
+            syn::parse_quote! {
+                mod pga2d {
+                    use std::ops::BitXor;
+
+                    type Field = f32;
+
+                    square!(e0, 0);
+                    square!(e1, 1);
+                    square!(e2, 1);
+
+                    shape!(Line, Mv<e1, e2, e0>);
+                    shape!(Point, Mv<e01, e20, e12>);
+
+                    #[reify(Line as A)]
+                    #[reify(Line as B)]
+                    impl BitXor<B> for A {
+                        type Output = impl Line;
+                        fn bitxor(self, line: B) -> Self::Output {
+                            self ^ line
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap_err();
+        let messages = err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[1],
+            "dropped reify variant A = Mv_e1_e2_e0, B = Mv_e1_e2_e0: \
+             computed battery Mv_e01_e02_e12 is not `impl Line`, closest shapes: Mv_e1_e2_e0"
+        );
+    }
 }
@@ -1,41 +1,6 @@
-use super::{BladeValue, reifier::ShapeCast};
-use crate::err;
-use crate::geometry::Blank;
-use quote::format_ident;
-use syn::parse::Parse;
-
-impl Parse for BladeValue {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let syn::FieldValue {
-            attrs,
-            member: syn::Member::Named(ident),
-            colon_token: Some(colon),
-            expr,
-        } = input.parse()?
-        else {
-            return Err(err!(input, "unrecognized blade value"));
-        };
-        let blade: Blank = ident.to_string().parse()?;
-        Ok(Self {
-            attrs,
-            blade,
-            colon,
-            expr,
-        })
-    }
-}
-
-impl From<BladeValue> for syn::FieldValue {
-    fn from(value: BladeValue) -> Self {
-        let bl = value.blade;
-        Self {
-            attrs: value.attrs,
-            member: syn::Member::Named(format_ident!("{bl}")),
-            colon_token: Some(value.colon),
-            expr: value.expr,
-        }
-    }
-}
+use super::{Options, reifier::ShapeCast};
+use reefer_core::{err, shader::Lang};
+use syn::{parse::Parse, punctuated::Punctuated};
 
 impl Parse for ShapeCast {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -52,3 +17,45 @@ impl Parse for ShapeCast {
         })
     }
 }
+
+impl Parse for Options {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut options = Options::default();
+        for ident in Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated(input)? {
+            let lang = match ident.to_string().as_str() {
+                "selftest" => {
+                    options.selftest = true;
+                    continue;
+                }
+                "repr_c" => {
+                    options.repr_c = true;
+                    continue;
+                }
+                "bytemuck" => {
+                    options.bytemuck = true;
+                    continue;
+                }
+                "serde" => {
+                    options.serde = true;
+                    continue;
+                }
+                "no_comparisons" => {
+                    options.no_comparisons = true;
+                    continue;
+                }
+                "ffi" => {
+                    options.ffi = true;
+                    options.repr_c = true;
+                    continue;
+                }
+                "wgsl" => Lang::Wgsl,
+                "glsl" => Lang::Glsl,
+                _ => return Err(err!(ident, format!("unknown option `{ident}`"))),
+            };
+            if !options.shaders.contains(&lang) {
+                options.shaders.push(lang)
+            }
+        }
+        Ok(options)
+    }
+}
@@ -1,16 +1,29 @@
 use std::{collections::HashMap, iter::FusedIterator};
 
-use crate::{
+use itertools::{Either, Itertools, MultiProduct};
+use proc_macro2::{Literal, Span};
+use quote::{ToTokens, format_ident, quote};
+use reefer_core::{
     cas::CasExpr,
     err,
-    geometry::{Blade, Blank, Blunt, CanonMap, Honed, Mvect, Shape, ShapeMap, SquareMap},
+    geometry::{
+        Battery, Blade, BladeValue, Blank, Blunt, CanonMap, Mvect, Shape, ShapeMap, SquareMap,
+    },
+};
+use syn::{
+    parse::Parse, parse_quote, punctuated::Punctuated, spanned::Spanned, visit_mut::VisitMut,
 };
-use itertools::{Either, Itertools, MultiProduct};
-use proc_macro2::Span;
-use quote::format_ident;
-use syn::{parse::Parse, punctuated::Punctuated, spanned::Spanned, visit_mut::VisitMut};
 
-use super::BladeValue;
+use super::{
+    Options,
+    callee::{Callee, CalleeMap},
+    diagnostic::Dropped,
+    ffi::ffi_items,
+    function::{ReifiedFn, is_reify_attr},
+    selftest::selftest_mod,
+    shader::shader_item,
+    variant::{Input, Operand, Variant},
+};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -25,6 +38,8 @@ pub struct BuildSpec {
     canons: CanonMap,
     shapes: ShapeMap,
     squares: SquareMap,
+    /// keep the reified variants, for the selftests and shaders
+    capture: bool,
 }
 
 impl TryFrom<&mut syn::ItemMod> for BuildSpec {
@@ -34,6 +49,7 @@ impl TryFrom<&mut syn::ItemMod> for BuildSpec {
             canons: Default::default(),
             shapes: Default::default(),
             squares: Default::default(),
+            capture: false,
         };
         let Some((brace, items)) = mod_.content.take() else {
             return Err(err!(mod_, "bare module not supported"));
@@ -54,21 +70,17 @@ impl TryFrom<&mut syn::ItemMod> for BuildSpec {
                 _ => new_items.push(item),
             }
         }
+        spec.squares.hone_ps();
+        new_items.extend(spec.squares.expand_consts());
         mod_.content = Some((brace, new_items));
-        // restore the blade invariants for the psuedoscalar
-        let Blade::Pos(Honed(frame)) = std::mem::take(&mut spec.squares.1) else {
-            unreachable!()
-        };
-        let ps: Blunt = Blank::Pos(frame).into();
-        let ps: Blade = ps.hone(&spec.squares);
-        spec.squares.1 = ps;
         // build canonical form lookup
         spec.canons = spec.shapes.clone().into_canon_map(&spec.squares);
         Ok(spec)
     }
 }
 impl BuildSpec {
-    pub fn reify_mod(self, mod_: &mut syn::ItemMod) -> syn::Result<()> {
+    pub fn reify_mod(mut self, mod_: &mut syn::ItemMod, options: &Options) -> syn::Result<()> {
+        self.capture = options.selftest || !options.shaders.is_empty() || options.ffi;
         let Some((brace, items)) = mod_.content.take() else {
             return Err(err!(mod_, "bare module not supported"));
         };
@@ -76,29 +88,177 @@ impl BuildSpec {
         new_items.push(syn::Item::Trait(syn::parse_quote!(
             trait Mv {}
         )));
+        // desugar reified free fns up front so every reified fn can be called from the others
+        let mut desugared = vec![];
         for item in items {
+            match item {
+                syn::Item::Fn(fn_) if fn_.attrs.iter().any(is_reify_attr) => {
+                    let ReifiedFn {
+                        trait_,
+                        impl_,
+                        wrapper,
+                    } = fn_.try_into()?;
+                    desugared.push(syn::Item::Trait(trait_));
+                    desugared.push(syn::Item::Fn(wrapper));
+                    desugared.push(syn::Item::Impl(impl_));
+                }
+                _ => desugared.push(item),
+            }
+        }
+        let mut callees = CalleeMap::default();
+        let mut consts = vec![]; // `const X: Field` items act as scalars
+        let mut const_values = vec![];
+        let mut field = None;
+        for item in &desugared {
+            match item {
+                syn::Item::Type(type_) if type_.ident == "Field" => field = Some(type_.clone()),
+                syn::Item::Impl(impl_) => callees.insert_impl(impl_)?,
+                syn::Item::Const(const_) if is_field(&const_.ty) => {
+                    consts.push(const_.ident.clone());
+                    if let Ok(value) = CasExpr::try_from(*const_.expr.clone()) {
+                        const_values.push((const_.ident.clone(), value))
+                    }
+                }
+                _ => (),
+            }
+        }
+        let shapes: HashMap<_, _> = self
+            .shapes
+            .all_shapes()
+            .into_iter()
+            .map(|shape| (format_ident!("{shape}"), shape))
+            .collect();
+        // shapes the module already formats itself
+        let displayed = desugared
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Impl(impl_) => implementor(impl_, "Display"),
+                _ => None,
+            })
+            .collect_vec();
+        // `Pod` needs a `#[repr(C)]`, `Copy` struct
+        let pod = options.bytemuck;
+        let mut variants = vec![];
+        let mut uses_blades = false;
+        let mut uses_fmt_terms = false;
+        for item in desugared {
             match item {
                 syn::Item::Impl(impl_) => {
-                    let span = impl_.impl_token.span;
-                    let mut is_empty = true;
-                    for result_item in self.reify_impl(impl_) {
-                        is_empty = false;
-                        new_items.push(result_item?);
+                    uses_blades |= self.push_reified_impl(
+                        impl_,
+                        &callees,
+                        &consts,
+                        &mut new_items,
+                        &mut variants,
+                    )?
+                }
+                syn::Item::Struct(mut struct_) if shapes.contains_key(&struct_.ident) => {
+                    let attrs = &mut struct_.attrs;
+                    if (options.repr_c || pod) && !attrs.iter().any(|a| a.path().is_ident("repr")) {
+                        attrs.push(parse_quote!(#[repr(C)]));
+                    }
+                    if pod {
+                        let missing = ["Clone", "Copy"]
+                            .map(|t| (!derives(attrs, t)).then_some(format_ident!("{t}")));
+                        let missing = missing.into_iter().flatten().collect_vec();
+                        if !missing.is_empty() {
+                            attrs.push(parse_quote!(#[derive(#(#missing),*)]));
+                        }
+                    }
+                    let zero_copy = attrs.iter().any(is_repr_c);
+                    let shape = &shapes[&struct_.ident];
+                    let display = !displayed.contains(&struct_.ident);
+                    new_items.push(syn::Item::Struct(struct_));
+                    new_items.push(shape.expand_get(&self.squares));
+                    if display {
+                        new_items.push(shape.expand_display());
+                        uses_fmt_terms = true;
                     }
-                    if is_empty {
-                        return Err(err!(
-                            span,
-                            "no code was generated for this impl, check your shape bounds"
-                        ));
+                    new_items.extend(shape.expand_arrays(zero_copy, pod));
+                    if options.serde {
+                        new_items.extend(shape.expand_serde(&self.squares));
                     }
                 }
                 _ => new_items.push(item),
             }
         }
+        if uses_fmt_terms {
+            new_items.push(Shape::expand_fmt_terms());
+        }
+        if uses_blades {
+            new_items.push(reefer_core::blades::expand());
+        }
+        if !options.no_comparisons {
+            // equality between shapes of a family
+            let derived = new_items
+                .iter()
+                .filter_map(|item| match item {
+                    syn::Item::Struct(struct_) if derives(&struct_.attrs, "PartialEq") => {
+                        Some(struct_.ident.clone())
+                    }
+                    _ => None,
+                })
+                .collect();
+            new_items.extend(self.shapes.expand_comparisons(&self.squares, &derived)?);
+        }
+        if options.selftest {
+            new_items.extend(selftest_mod(&self.squares, &variants));
+        }
+        for &lang in &options.shaders {
+            let item = shader_item(lang, &mod_.ident, &self.shapes, &const_values, &variants);
+            new_items.push(item);
+        }
+        if options.serde {
+            new_items.push(SquareMap::expand_blade_parser());
+        }
+        if options.ffi {
+            let items = ffi_items(&mod_.ident, &self.shapes, field.as_ref(), &variants)?;
+            new_items.extend(items);
+        }
         mod_.content = Some((brace, new_items));
         Ok(())
     }
-    fn reify_impl(&self, mut impl_: syn::ItemImpl) -> impl Iterator<Item = syn::Result<syn::Item>> {
+    /// whether the reified fns call into `mod blades`
+    fn push_reified_impl(
+        &self,
+        impl_: syn::ItemImpl,
+        callees: &CalleeMap,
+        consts: &[syn::Ident],
+        new_items: &mut Vec<syn::Item>,
+        variants: &mut Vec<Variant>,
+    ) -> syn::Result<bool> {
+        let span = impl_.impl_token.span;
+        let mut is_empty = true;
+        let mut reified = self.reify_impl(impl_, callees, consts);
+        for result_item in reified.by_ref() {
+            is_empty = false;
+            new_items.push(result_item?);
+        }
+        let Either::Right(mut reifier) = reified else {
+            return Ok(false);
+        };
+        if is_empty {
+            let e = err!(
+                span,
+                "no code was generated for this impl, check your shape bounds"
+            );
+            return Err(reifier.dropped.into_iter().fold(e, |mut e, dropped| {
+                e.combine(dropped.into_error());
+                e
+            }));
+        }
+        variants.append(&mut reifier.variants);
+        if reifier.verbose {
+            new_items.extend(reifier.dropped.into_iter().map(Dropped::into_warning));
+        }
+        Ok(reifier.uses_blades)
+    }
+    fn reify_impl<'a>(
+        &'a self,
+        mut impl_: syn::ItemImpl,
+        callees: &'a CalleeMap,
+        consts: &'a [syn::Ident],
+    ) -> Either<std::iter::Once<syn::Result<syn::Item>>, Reifier<'a>> {
         // get the reify attrs
         let mut shape_binds = vec![];
         let mut other_attrs = vec![];
@@ -133,6 +293,9 @@ impl BuildSpec {
                     .multi_cartesian_product(),
                 reified_types: vec![],
                 reified_args: vec![],
+                matrices: vec![],
+                subspaces: vec![],
+                uses_blades: false,
                 assoc_types: Default::default(),
                 rec_shape: None,
                 ret_shape: None,
@@ -140,9 +303,20 @@ impl BuildSpec {
                 squares: &self.squares,
                 shapes: &self.shapes,
                 canons: &self.canons,
+                callees,
+                consts,
+                inline_depth: 0,
+                reifying: vec![],
+                scalar_ret: false,
+                dropped: vec![],
                 verbose,
+                capture: self.capture,
+                tail_mv: None,
+                output: None,
+                pending: vec![],
+                variants: vec![],
             };
-            Either::Right(items.into_iter())
+            Either::Right(items)
         }
     }
     fn reify_shape_binds(&self, cast: ShapeCast) -> Vec<(syn::Type, Shape)> {
@@ -153,6 +327,109 @@ impl BuildSpec {
     }
 }
 
+fn derives(attrs: &[syn::Attribute], trait_: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .any(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+                .is_ok_and(|paths| paths.iter().any(|path| path.is_ident(trait_)))
+        })
+}
+
+/// the type a plain `impl Trait for Type` of the module is for, whatever the path to `Trait`
+fn implementor(impl_: &syn::ItemImpl, trait_: &str) -> Option<syn::Ident> {
+    let (_, path, _) = impl_.trait_.as_ref()?;
+    if path.segments.last()?.ident != trait_ {
+        return None;
+    }
+    match impl_.self_ty.as_ref() {
+        syn::Type::Path(ty) => ty.path.get_ident().cloned(),
+        _ => None,
+    }
+}
+
+fn is_repr_c(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("repr")
+        && attr
+            .parse_args_with(Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated)
+            .is_ok_and(|reprs| reprs.iter().any(|repr| repr == "C"))
+}
+
+fn is_field(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("Field"))
+}
+
+fn is_bool(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("bool"))
+}
+
+fn is_from_matrix(func: &syn::Expr) -> bool {
+    matches!(func, syn::Expr::Path(func) if func.path.is_ident("from_matrix"))
+}
+
+/// `[[m_0_0, ..], ..]`
+fn matrix_expr(m: Vec<Vec<CasExpr>>) -> syn::Result<syn::Expr> {
+    let rows = m
+        .into_iter()
+        .map(|row| {
+            let row = row
+                .into_iter()
+                .map(syn::Expr::try_from)
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote!([#(#row),*]))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(parse_quote!([#(#rows),*]))
+}
+
+/// `[x_0, x_1, ..]`, the coefficients of `mv` in the layout of `blades`
+fn bits_expr(mv: Mvect<CasExpr>) -> syn::Result<syn::Expr> {
+    let bits: Vec<syn::Expr> = mv
+        .to_bits()
+        .into_iter()
+        .map(TryInto::try_into)
+        .try_collect()?;
+    Ok(parse_quote!([#(#bits),*]))
+}
+
+/// the lengths of a `[[Field; N]; M]`
+fn field_matrix(ty: &syn::Type) -> Option<[&syn::Expr; 2]> {
+    let syn::Type::Array(rows) = ty else {
+        return None;
+    };
+    let syn::Type::Array(row) = rows.elem.as_ref() else {
+        return None;
+    };
+    is_field(&row.elem).then_some([&rows.len, &row.len])
+}
+
+/// guards against reified fns that (indirectly) call themselves
+const MAX_INLINE_DEPTH: usize = 32;
+
+/// a reified value that isn't a multivector, turned into the tail expression of the fn
+#[derive(Debug)]
+enum Output<'a> {
+    /// the value of the last `mv.blade` access, which is reified in place
+    Field(CasExpr),
+    /// the value of a `to_matrix()` tail
+    Matrix(Vec<Vec<CasExpr>>),
+    /// the parts of a `split()` tail
+    Tuple(Vec<Mvect<'a, CasExpr>>),
+    /// the factors of a `factorize()` tail
+    Array(Vec<Mvect<'a, CasExpr>>),
+}
+
+/// the shapes the return type of the fn being reified is reified to
+#[derive(Debug)]
+enum RetShape {
+    Mv(Shape),
+    /// `(impl A, impl B)`
+    Tuple(Vec<Shape>),
+    /// `[impl A; N]`
+    Array(Shape, usize),
+}
+
 #[derive(Debug)]
 struct Reifier<'a> {
     err: Option<syn::Error>,
@@ -160,15 +437,35 @@ struct Reifier<'a> {
     template: syn::ItemImpl,
     reified_types_iter: MultiProduct<std::vec::IntoIter<(syn::Type, Shape)>>,
     reified_types: Vec<(syn::Type, Shape)>,
-    reified_args: Vec<(syn::Ident, Shape)>,
+    reified_args: Vec<(syn::Ident, Mvect<'a, CasExpr>)>,
+    /// `[[Field; DIM]; DIM]` args, destructured into their entries
+    matrices: Vec<(syn::Ident, Vec<Vec<CasExpr>>)>,
+    /// `let` bindings of the runtime meets, joins and factorizations, in the order they're needed
+    subspaces: Vec<syn::Stmt>,
+    uses_blades: bool,
     rec_shape: Option<Shape>,
-    ret_shape: Option<Shape>,
+    ret_shape: Option<RetShape>,
     assoc_types: HashMap<syn::Ident, syn::Type>,
     squares: &'a SquareMap,
     shapes: &'a ShapeMap,
     canons: &'a CanonMap,
+    callees: &'a CalleeMap,
+    consts: &'a [syn::Ident],
+    inline_depth: usize,
+    /// the fn being reified and the ones inlined into it
+    reifying: Vec<syn::Ident>,
+    scalar_ret: bool,
     mv_cas: Option<Mvect<'a, CasExpr>>,
+    dropped: Vec<Dropped>,
     verbose: bool,
+    capture: bool,
+    /// the final value of the fn being reified
+    tail_mv: Option<Mvect<'a, CasExpr>>,
+    /// the value of the last expression when it isn't a multivector
+    output: Option<Output<'a>>,
+    /// fns of the variant being reified
+    pending: Vec<Variant>,
+    variants: Vec<Variant>,
 }
 impl Iterator for Reifier<'_> {
     type Item = syn::Result<syn::Item>;
@@ -177,7 +474,11 @@ impl Iterator for Reifier<'_> {
             let mut impl_ = self.template.clone();
             self.reified_types = self.reified_types_iter.next()?;
             self.visit_item_impl_mut(&mut impl_);
+            let pending = std::mem::take(&mut self.pending);
             if self.reifiable {
+                if self.err.is_none() {
+                    self.variants.extend(pending);
+                }
                 return Some(self.err.take().map_or(Ok(syn::Item::Impl(impl_)), Err));
             }
             self.reifiable = true;
@@ -202,7 +503,6 @@ macro_rules! unwrap_or_ret {
         }
     };
 }
-// todo should also pick up literals?
 macro_rules! visit_mvect {
     ($elf:expr, $expr:expr) => {{
         $elf.visit_expr_mut($expr);
@@ -222,9 +522,27 @@ macro_rules! visit_un {
         $elf.mv_cas = Some(lhs.$op());
     }};
 }
-macro_rules! visit_lit_op {
-    ($elf:expr, $op:ident, $lhs:expr, $rhs:expr) => {{
-        let lhs = visit_mvect!($elf, $lhs);
+/// takes the `$i`th reified operand of a method call, the receiver being the 0th
+macro_rules! operand {
+    ($ops:expr, $i:expr) => {
+        unwrap_or_ret!($ops.get_mut($i).and_then(Option::take))
+    };
+}
+macro_rules! method_un {
+    ($elf:expr, $op:ident, $ops:expr) => {{
+        let lhs = operand!($ops, 0);
+        $elf.mv_cas = Some(lhs.$op());
+    }};
+}
+macro_rules! method_bin {
+    ($elf:expr, $op:ident, $ops:expr) => {{
+        let lhs = operand!($ops, 0);
+        let rhs = operand!($ops, 1);
+        $elf.mv_cas = Some(lhs.$op(rhs));
+    }};
+}
+macro_rules! method_lit_op {
+    ($elf:expr, $op:ident, $ops:expr, $rhs:expr) => {{
         let n: usize = match $rhs {
             syn::Expr::Lit(syn::ExprLit {
                 lit: syn::Lit::Int(n),
@@ -235,14 +553,15 @@ macro_rules! visit_lit_op {
             },
             expr => return $elf.err = Some(err!(expr, "expected usize")),
         };
+        let lhs = operand!($ops, 0);
         $elf.mv_cas = Some(lhs.$op(n));
     }};
 }
-macro_rules! visit_duality {
-    ($elf:expr, $meth:expr, $fun:ident) => {{
+macro_rules! method_duality {
+    ($elf:expr, $meth:expr, $fun:ident, $ops:expr) => {{
         match $meth.args.first() {
             None => {
-                let mv = visit_mvect!($elf, $meth.receiver.as_mut());
+                let mv = operand!($ops, 0);
                 $elf.mv_cas = Some(mv.$fun($elf.squares.1.clone()))
             }
             Some(syn::Expr::Path(syn::ExprPath { path, .. })) => {
@@ -252,7 +571,7 @@ macro_rules! visit_duality {
                 };
                 let ps: Blunt = ps.into();
                 let ps = ps.hone($elf.squares);
-                let mv = visit_mvect!($elf, $meth.receiver.as_mut());
+                let mv = operand!($ops, 0);
                 $elf.mv_cas = Some(mv.$fun(ps))
             }
             _ => return $elf.err = Some(err!($meth.args, "unrecognized psuedoscalar")),
@@ -277,23 +596,59 @@ impl VisitMut for Reifier<'_> {
         self.save_assoc_types(&impl_.items);
         syn::visit_mut::visit_item_impl_mut(self, impl_);
         self.update_assoc_types(&mut impl_.items);
+        self.save_variant_paths(impl_);
     }
     fn visit_impl_item_fn_mut(&mut self, impl_fn: &mut syn::ImplItemFn) {
+        self.scalar_ret =
+            matches!(&impl_fn.sig.output, syn::ReturnType::Type(_, ty) if is_field(ty));
+        let inputs = self.capture.then(|| self.variant_inputs(impl_fn)).flatten();
+        self.reifying.push(impl_fn.sig.ident.clone());
         syn::visit_mut::visit_impl_item_fn_mut(self, impl_fn);
-        self.save_return_type(&impl_fn.sig.output);
+        self.reifying.pop();
+        if let Some((inputs, body)) = inputs {
+            self.save_variant(&impl_fn.sig, inputs, body);
+        }
+        self.save_return_type(&mut impl_fn.sig.output);
+        let subspaces = std::mem::take(&mut self.subspaces);
+        self.uses_blades |= !subspaces.is_empty();
+        impl_fn.block.stmts.splice(0..0, subspaces);
+        self.destructure_matrices(&mut impl_fn.block);
         self.reified_args.clear(); // arg cache only valid per function
+        // reified bodies expand into field products no matter which operator is implemented,
+        // and some variants simplify an argument away entirely
+        impl_fn.attrs.extend([
+            parse_quote!(#[allow(clippy::suspicious_arithmetic_impl)]),
+            parse_quote!(#[allow(unused_variables)]),
+        ]);
     }
     fn visit_stmt_mut(&mut self, stmt: &mut syn::Stmt) {
         self.ret_shape = None;
+        self.output = None;
         let span = stmt.span();
         match stmt {
             syn::Stmt::Local(_local) => todo!(),
             syn::Stmt::Expr(expr, semi_token) => {
+                let is_field_access = matches!(expr, syn::Expr::Field(_));
                 self.visit_expr_mut(expr);
                 if semi_token.is_none() {
                     self.save_return_shape(span);
                     if let Some(mv_cas) = self.mv_cas.take() {
-                        *expr = unwrap_or_err!(self, mv_cas.try_into_expr(self.canons, span))
+                        self.save_tail_mv(&mv_cas);
+                        *expr = unwrap_or_err!(self, self.tail_expr(mv_cas, span))
+                    } else {
+                        match self.output.take() {
+                            Some(Output::Field(value)) if is_field_access => {
+                                self.save_tail_mv(&Mvect::scalar(value, self.squares))
+                            }
+                            Some(Output::Matrix(m)) => *expr = unwrap_or_err!(self, matrix_expr(m)),
+                            Some(Output::Tuple(parts)) => {
+                                *expr = unwrap_or_err!(self, self.tuple_expr(parts, span))
+                            }
+                            Some(Output::Array(factors)) => {
+                                *expr = unwrap_or_err!(self, self.array_expr(factors, span))
+                            }
+                            _ => (),
+                        }
                     }
                 }
             }
@@ -302,7 +657,8 @@ impl VisitMut for Reifier<'_> {
                 if stmt_mac.semi_token.is_none() {
                     self.save_return_shape(span);
                     if let Some(mv_cas) = self.mv_cas.take() {
-                        let expr = unwrap_or_err!(self, mv_cas.try_into_expr(self.canons, span));
+                        self.save_tail_mv(&mv_cas);
+                        let expr = unwrap_or_err!(self, self.tail_expr(mv_cas, span));
                         *stmt = syn::Stmt::Expr(expr, stmt_mac.semi_token);
                     }
                 }
@@ -338,46 +694,154 @@ impl VisitMut for Reifier<'_> {
                 let blank: Blank = unwrap_or_err!(self, ident.clone().try_into());
                 let blade: Blade = Blunt::from(blank).hone(self.squares);
                 let value = unwrap_or_ret!(mv.take(&blade));
+                if self.capture {
+                    self.output = Some(Output::Field(value.clone()))
+                }
                 *expr = unwrap_or_err!(self, value.try_into())
             }
             syn::Expr::MethodCall(expr) => {
-                let rec = expr.receiver.as_mut();
-                match expr.method.to_string().as_str() {
-                    "commutate" => visit_bin!(self, commutate, rec, &mut expr.args[0]),
-                    "anticomm" => visit_bin!(self, anticomm, rec, &mut expr.args[0]),
-                    "sandwich" => visit_bin!(self, sandwich, rec, &mut expr.args[0]),
-                    "inverse" => visit_un!(self, inv, rec),
-                    "grade" => visit_lit_op!(self, grade, rec, &mut expr.args[0]),
-                    "pow" => visit_lit_op!(self, pow, rec, &mut expr.args[0]),
-                    "norm" => visit_un!(self, norm, rec),
-                    "normed" => visit_un!(self, normed, rec),
-                    "exp" => visit_un!(self, exp, rec),
-                    "log" => visit_un!(self, log, rec),
-                    "sqrt" => visit_un!(self, sqrt, rec),
-                    "add" => visit_bin!(self, add, rec, &mut expr.args[0]),
-                    "sub" => visit_bin!(self, sub, rec, &mut expr.args[0]),
-                    "mul" => visit_bin!(self, mul, rec, &mut expr.args[0]),
-                    "wedge" => visit_bin!(self, wedge, rec, &mut expr.args[0]),
-                    "regressive" => visit_bin!(self, regressive, rec, &mut expr.args[0]),
-                    "dot" => visit_bin!(self, dot, rec, &mut expr.args[0]),
-                    "fat_dot" => visit_bin!(self, fat_dot, rec, &mut expr.args[0]),
-                    "div" => visit_bin!(self, div, rec, &mut expr.args[0]),
-                    "ldiv" => visit_bin!(self, ldiv, rec, &mut expr.args[0]),
-                    "lcontract" => visit_bin!(self, lcontract, rec, &mut expr.args[0]),
-                    "rcontract" => visit_bin!(self, rcontract, rec, &mut expr.args[0]),
-                    "dual" => visit_duality!(self, expr, dual),
-                    "undual" => visit_duality!(self, expr, undual),
-                    "ldual" => visit_duality!(self, expr, ldual),
-                    "lundual" => visit_duality!(self, expr, lundual),
-                    "neg" => visit_un!(self, neg, rec),
-                    "aut" => visit_un!(self, aut, rec),
-                    "rev" => visit_un!(self, rev, rec),
-                    "conj" => visit_un!(self, conj, rec),
-                    "simplify" => visit_un!(self, simplify, rec),
-                    _ => self.err = Some(err!(expr.method, "Unrecognized method")),
+                let method = expr.method.to_string();
+                // the receiver and args are reified once, for the builtin or the fn shadowing it
+                let mut ops = std::iter::once(expr.receiver.as_mut())
+                    .chain(expr.args.iter_mut())
+                    .map(|op| {
+                        self.visit_expr_mut(op);
+                        self.mv_cas.take()
+                    })
+                    .collect_vec();
+                // reified fns of the module shadow the builtins they accept the args of
+                let builtin = match self.shadows_builtin(&expr.method, &ops) {
+                    true => "",
+                    false => method.as_str(),
+                };
+                match builtin {
+                    "commutate" => method_bin!(self, commutate, ops),
+                    "anticomm" => method_bin!(self, anticomm, ops),
+                    "sandwich" => method_bin!(self, sandwich, ops),
+                    "project" => method_bin!(self, project, ops),
+                    "reject" => method_bin!(self, reject, ops),
+                    "reflect" => method_bin!(self, reflect, ops),
+                    "antimul" => method_bin!(self, antimul, ops),
+                    "antiwedge" => method_bin!(self, antiwedge, ops),
+                    "antidot" => method_bin!(self, antidot, ops),
+                    "antisandwich" => method_bin!(self, antisandwich, ops),
+                    "inverse" => method_un!(self, inv, ops),
+                    "grade" => method_lit_op!(self, grade, ops, &expr.args[0]),
+                    "pow" => method_lit_op!(self, pow, ops, &expr.args[0]),
+                    "norm" => method_un!(self, norm, ops),
+                    "normed" => method_un!(self, normed, ops),
+                    "bulk" => method_un!(self, bulk, ops),
+                    "weight" => method_un!(self, weight, ops),
+                    "bulk_norm" => method_un!(self, bulk_norm, ops),
+                    "weight_norm" => method_un!(self, weight_norm, ops),
+                    "unitize" => method_un!(self, unitize, ops),
+                    "exp" => method_un!(self, exp, ops),
+                    "log" => method_un!(self, log, ops),
+                    "sqrt" => method_un!(self, sqrt, ops),
+                    "add" => method_bin!(self, add, ops),
+                    "sub" => method_bin!(self, sub, ops),
+                    "mul" => method_bin!(self, mul, ops),
+                    "wedge" => method_bin!(self, wedge, ops),
+                    "regressive" => method_bin!(self, regressive, ops),
+                    "join" | "meet" if expr.args.len() == 1 => {
+                        let lhs = operand!(ops, 0);
+                        let rhs = operand!(ops, 1);
+                        let mv = self.bind_subspace(&expr.method, lhs, rhs);
+                        self.mv_cas = Some(unwrap_or_err!(self, mv))
+                    }
+                    "dot" => method_bin!(self, dot, ops),
+                    "fat_dot" => method_bin!(self, fat_dot, ops),
+                    "div" => method_bin!(self, div, ops),
+                    "ldiv" => method_bin!(self, ldiv, ops),
+                    "lcontract" => method_bin!(self, lcontract, ops),
+                    "rcontract" => method_bin!(self, rcontract, ops),
+                    "dual" => method_duality!(self, expr, dual, ops),
+                    "undual" => method_duality!(self, expr, undual, ops),
+                    "ldual" => method_duality!(self, expr, ldual, ops),
+                    "lundual" => method_duality!(self, expr, lundual, ops),
+                    "neg" => method_un!(self, neg, ops),
+                    "aut" => method_un!(self, aut, ops),
+                    "rev" => method_un!(self, rev, ops),
+                    "conj" => method_un!(self, conj, ops),
+                    "right_complement" => method_un!(self, right_complement, ops),
+                    "left_complement" => method_un!(self, left_complement, ops),
+                    "bulk_right_complement" => method_un!(self, bulk_right_complement, ops),
+                    "bulk_left_complement" => method_un!(self, bulk_left_complement, ops),
+                    "weight_right_complement" => method_un!(self, weight_right_complement, ops),
+                    "weight_left_complement" => method_un!(self, weight_left_complement, ops),
+                    "hodge" => method_un!(self, hodge, ops),
+                    "simplify" => method_un!(self, simplify, ops),
+                    "outermorphism" if expr.args.len() == 1 => {
+                        let mv = operand!(ops, 0);
+                        let m = unwrap_or_err!(self, self.matrix_arg(&expr.args[0]));
+                        self.mv_cas = Some(mv.outermorphism(m))
+                    }
+                    "flip" => {
+                        let grades = unwrap_or_err!(self, self.squares.parse_grades(&expr.args));
+                        let mv = operand!(ops, 0);
+                        self.mv_cas = Some(mv.flip(&grades))
+                    }
+                    "grade_range" if expr.args.len() == 1 => {
+                        let range = self.squares.parse_grade_range(&expr.args[0]);
+                        let range = unwrap_or_err!(self, range);
+                        let mv = operand!(ops, 0);
+                        self.mv_cas = Some(mv.grade_range(range))
+                    }
+                    "even" => method_un!(self, even, ops),
+                    "odd" => method_un!(self, odd, ops),
+                    "split" if expr.args.is_empty() => {
+                        if self.squares.signature().len() > 5 {
+                            let msg = "split is only implemented up to 5 dimensions";
+                            return self.err = Some(err!(expr.method, msg));
+                        }
+                        let mv = operand!(ops, 0);
+                        self.output = Some(Output::Tuple(mv.split()))
+                    }
+                    "factorize" if expr.args.is_empty() => {
+                        let mv = operand!(ops, 0);
+                        let factors = self.bind_factors(&expr.method, mv);
+                        self.output = Some(Output::Array(unwrap_or_err!(self, factors)))
+                    }
+                    "to_matrix" if expr.args.is_empty() => {
+                        let mv = operand!(ops, 0);
+                        self.output = Some(Output::Matrix(mv.to_matrix()))
+                    }
+                    "get" if expr.args.len() == 1 => {
+                        let mv = operand!(ops, 0);
+                        let blade = unwrap_or_err!(self, self.basis_blade(&expr.args[0]));
+                        self.mv_cas = Some(mv.component(&blade))
+                    }
+                    _ if self.callees.get(&expr.method).is_empty() => {
+                        self.err = Some(err!(expr.method, "Unrecognized method"))
+                    }
+                    _ => {
+                        let args = unwrap_or_ret!(ops.into_iter().collect::<Option<Vec<_>>>());
+                        let mv = self.inline_call(&expr.method, args, span);
+                        self.mv_cas = Some(unwrap_or_err!(self, mv))
+                    }
+                }
+            }
+            syn::Expr::Call(call) if is_from_matrix(&call.func) && call.args.len() == 1 => {
+                let m = unwrap_or_err!(self, self.matrix_arg(&call.args[0]));
+                self.mv_cas = Some(Mvect::from_matrix(m, self.squares))
+            }
+            syn::Expr::Call(call) if self.is_callee(&call.func) => {
+                let syn::Expr::Path(func) = call.func.as_ref() else {
+                    unreachable!()
+                };
+                let ident = func.path.segments[0].ident.clone();
+                let mut args = vec![];
+                for arg in call.args.iter_mut() {
+                    args.push(visit_mvect!(self, arg));
                 }
+                let mv = self.inline_call(&ident, args, span);
+                self.mv_cas = Some(unwrap_or_err!(self, mv))
+            }
+            syn::Expr::Index(index) => {
+                let mv = visit_mvect!(self, index.expr.as_mut());
+                let blade = unwrap_or_err!(self, self.basis_blade(&index.index));
+                self.mv_cas = Some(mv.component(&blade))
             }
-            syn::Expr::Index(_expr) => todo!(),
             syn::Expr::Unary(expr) => match expr.op {
                 syn::UnOp::Neg(_) => visit_un!(self, neg, expr.expr.as_mut()),
                 syn::UnOp::Not(_) => {
@@ -386,10 +850,10 @@ impl VisitMut for Reifier<'_> {
                 }
                 _ => unimplemented!(),
             },
-            syn::Expr::Binary(expr) => {
-                let lhs = expr.left.as_mut();
-                let rhs = expr.right.as_mut();
-                match expr.op {
+            syn::Expr::Binary(bin) => {
+                let lhs = bin.left.as_mut();
+                let rhs = bin.right.as_mut();
+                match bin.op {
                     syn::BinOp::Add(_) => visit_bin!(self, add, lhs, rhs),
                     syn::BinOp::AddAssign(_) => unimplemented!(),
                     syn::BinOp::And(_) => unimplemented!(),
@@ -401,14 +865,27 @@ impl VisitMut for Reifier<'_> {
                     syn::BinOp::BitXorAssign(_) => unimplemented!(),
                     syn::BinOp::Div(_) => visit_bin!(self, div, lhs, rhs),
                     syn::BinOp::DivAssign(_) => unimplemented!(),
-                    syn::BinOp::Eq(_) => todo!(),
-                    syn::BinOp::Ge(_) => unimplemented!(),
-                    syn::BinOp::Gt(_) => unimplemented!(),
-                    syn::BinOp::Le(_) => unimplemented!(),
-                    syn::BinOp::Lt(_) => unimplemented!(),
+                    syn::BinOp::Eq(_) => {
+                        let (lhs, rhs) = (visit_mvect!(self, lhs), visit_mvect!(self, rhs));
+                        *expr = unwrap_or_err!(self, lhs.try_into_eq_expr(rhs))
+                    }
+                    op @ (syn::BinOp::Ge(_)
+                    | syn::BinOp::Gt(_)
+                    | syn::BinOp::Le(_)
+                    | syn::BinOp::Lt(_)) => {
+                        // only scalars are ordered
+                        let (lhs, rhs) = (visit_mvect!(self, lhs), visit_mvect!(self, rhs));
+                        let lhs = unwrap_or_err!(self, lhs.try_into_scalar_expr(span));
+                        let rhs = unwrap_or_err!(self, rhs.try_into_scalar_expr(span));
+                        *expr = parse_quote!((#lhs) #op (#rhs))
+                    }
                     syn::BinOp::Mul(_) => visit_bin!(self, mul, lhs, rhs),
                     syn::BinOp::MulAssign(_) => unimplemented!(),
-                    syn::BinOp::Ne(_) => todo!(),
+                    syn::BinOp::Ne(_) => {
+                        let (lhs, rhs) = (visit_mvect!(self, lhs), visit_mvect!(self, rhs));
+                        let eq = unwrap_or_err!(self, lhs.try_into_eq_expr(rhs));
+                        *expr = parse_quote!(!(#eq))
+                    }
                     syn::BinOp::Rem(_) => visit_bin!(self, sandwich, lhs, rhs),
                     syn::BinOp::RemAssign(_) => unimplemented!(),
                     syn::BinOp::Shl(_) => visit_bin!(self, lcontract, lhs, rhs),
@@ -422,8 +899,19 @@ impl VisitMut for Reifier<'_> {
             }
             syn::Expr::Path(expr) => {
                 let ident = unwrap_or_ret!(expr.path.get_ident()).clone();
-                let shape = unwrap_or_ret!(self.get_reified_arg(&ident)).clone();
-                self.mv_cas = Some(shape.into_mv_cas(ident, self.squares))
+                if self.consts.contains(&ident) {
+                    let value = CasExpr::var(ident);
+                    self.mv_cas = Some(Mvect::scalar(value, self.squares))
+                } else {
+                    self.mv_cas = Some(unwrap_or_ret!(self.get_reified_arg(&ident)).clone())
+                }
+            }
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(_) | syn::Lit::Float(_),
+                ..
+            }) => {
+                let value = unwrap_or_err!(self, CasExpr::try_from(expr.clone()));
+                self.mv_cas = Some(Mvect::scalar(value, self.squares))
             }
             expr => syn::visit_mut::visit_expr_mut(self, expr),
         }
@@ -444,11 +932,14 @@ impl VisitMut for Reifier<'_> {
     }
 }
 
-impl Reifier<'_> {
+impl<'a> Reifier<'a> {
     fn save_return_shape(&mut self, span: Span) {
+        if self.scalar_ret {
+            return; // the scalar part is returned as is
+        }
         if let Some(mv_cas) = self.mv_cas.clone() {
             let shape = unwrap_or_err!(self, mv_cas.try_into_shape(self.canons, span));
-            self.ret_shape = Some(shape) // update the return type
+            self.ret_shape = Some(RetShape::Mv(shape)) // update the return type
         }
     }
     fn save_receiver_shape(&mut self, ty: &syn::Type) {
@@ -456,78 +947,259 @@ impl Reifier<'_> {
     }
     fn save_receiver_arg(&mut self) {
         if let Some(shape) = self.rec_shape.clone() {
-            self.reified_args.push((format_ident!("self"), shape))
+            let ident = format_ident!("self");
+            let mv = shape.into_mv_cas(ident.clone(), self.squares);
+            self.reified_args.push((ident, mv))
         }
     }
     fn save_pat_ty_arg(&mut self, pat_ty: &mut syn::PatType) {
+        let syn::Pat::Ident(pat) = pat_ty.pat.as_ref() else {
+            return;
+        };
         if let Some(shape) = self.get_reified_shape(&pat_ty.ty) {
-            if let syn::Pat::Ident(pat) = pat_ty.pat.as_ref() {
-                self.reified_args.push((pat.ident.clone(), shape.clone()))
+            let mv = shape.clone().into_mv_cas(pat.ident.clone(), self.squares);
+            self.reified_args.push((pat.ident.clone(), mv))
+        } else if is_field(&pat_ty.ty) {
+            let mv = Mvect::scalar(CasExpr::var(&pat.ident), self.squares);
+            self.reified_args.push((pat.ident.clone(), mv))
+        } else if let Some(lens) = field_matrix(&pat_ty.ty) {
+            let dim = self.squares.signature().len();
+            let is_dim = |len: &syn::Expr| match len {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(n),
+                    ..
+                }) => n.base10_parse::<usize>().is_ok_and(|n| n == dim),
+                syn::Expr::Path(path) => path.path.is_ident("DIM"),
+                _ => false,
+            };
+            if !lens.into_iter().all(is_dim) {
+                return self.err = Some(err!(pat_ty.ty, "expected a `[[Field; DIM]; DIM]` matrix"));
+            }
+            let ident = &pat.ident;
+            let entries = (0..dim)
+                .map(|i| {
+                    (0..dim)
+                        .map(|j| CasExpr::var(format!("{ident}_{i}_{j}")))
+                        .collect()
+                })
+                .collect();
+            self.matrices.push((ident.clone(), entries))
+        }
+    }
+    /// binds `blades::join` or `blades::meet` of two blades, which factorizes at runtime.
+    /// the grades the result can have are fixed here, their blades are named `join0_3`
+    /// after the bits of their axes
+    fn bind_subspace(
+        &mut self,
+        method: &syn::Ident,
+        lhs: Mvect<'a, CasExpr>,
+        rhs: Mvect<'a, CasExpr>,
+    ) -> syn::Result<Mvect<'a, CasExpr>> {
+        let grades = match method == "join" {
+            true => lhs.join_grades(&rhs),
+            false => lhs.meet_grades(&rhs),
+        };
+        let (lhs, rhs) = (bits_expr(lhs)?, bits_expr(rhs)?);
+        let n = 1 << self.squares.signature().len();
+        let k = self.subspaces.len();
+        let names = (0..n).map(|bits: usize| format!("{method}{k}_{bits}"));
+        let pats = names.clone().enumerate().map(|(bits, name)| {
+            match grades.contains(&(bits.count_ones() as usize)) {
+                true => format_ident!("{name}").into_token_stream(),
+                false => quote!(_),
             }
+        });
+        let len = Literal::usize_unsuffixed(n);
+        self.subspaces.push(parse_quote! {
+            let [#(#pats),*]: [Field; #len] = blades::#method(&#lhs, &#rhs);
+        });
+        let bits = names.map(CasExpr::var).collect_vec();
+        Ok(Mvect::from_bits(&bits, grades, self.squares))
+    }
+    /// binds `blades::factors` of a blade, which projects the axes of its largest coefficient
+    /// at runtime. the `i`th factor is named `factorize0_i_1` after the bits of its axes
+    fn bind_factors(
+        &mut self,
+        method: &syn::Ident,
+        blade: Mvect<'a, CasExpr>,
+    ) -> syn::Result<Vec<Mvect<'a, CasExpr>>> {
+        let grade = match blade.single_grade() {
+            Some(grade) if grade > 0 => grade,
+            _ => return Err(err!(method, "expected a blade of a single nonzero grade")),
+        };
+        let blade = bits_expr(blade)?;
+        let n = 1 << self.squares.signature().len();
+        let k = self.subspaces.len();
+        let names = (0..grade)
+            .map(|i| {
+                (0..n)
+                    .map(|bits: usize| format!("{method}{k}_{i}_{bits}"))
+                    .collect_vec()
+            })
+            .collect_vec();
+        let rows = names.iter().map(|names| {
+            let pats = names
+                .iter()
+                .enumerate()
+                .map(|(bits, name)| match bits.count_ones() == 1 {
+                    true => format_ident!("{name}").into_token_stream(),
+                    false => quote!(_),
+                });
+            quote!([#(#pats),*])
+        });
+        let (len, count) = (
+            Literal::usize_unsuffixed(n),
+            Literal::usize_unsuffixed(grade),
+        );
+        self.subspaces.push(parse_quote! {
+            let [#(#rows),*]: [[Field; #len]; #count] = blades::factors(&#blade);
+        });
+        let factors = names.into_iter().map(|names| {
+            let bits = names.into_iter().map(CasExpr::var).collect_vec();
+            Mvect::from_bits(&bits, 1..=1, self.squares)
+        });
+        Ok(factors.collect())
+    }
+    /// `let [[m_0_0, ..], ..] = m;` so the reified body can use the entries of `m`
+    fn destructure_matrices(&mut self, block: &mut syn::Block) {
+        for (ident, _) in std::mem::take(&mut self.matrices) {
+            let dim = self.squares.signature().len();
+            let rows = (0..dim).map(|i| {
+                let row = (0..dim).map(|j| format_ident!("{ident}_{i}_{j}"));
+                quote!([#(#row),*])
+            });
+            block
+                .stmts
+                .insert(0, parse_quote!(let [#(#rows),*] = #ident;));
         }
     }
-    fn save_return_type(&mut self, output: &syn::ReturnType) {
-        let Some(shape) = self.ret_shape.take() else {
+    /// the final expression of a fn body, `-> Field` fns return the scalar part directly
+    fn tail_expr(&self, mv: Mvect<'a, CasExpr>, span: Span) -> syn::Result<syn::Expr> {
+        match self.scalar_ret {
+            true => mv.try_into_scalar_expr(span),
+            false => mv.try_into_expr(self.canons, span),
+        }
+    }
+    fn save_return_type(&mut self, output: &mut syn::ReturnType) {
+        let Some(ret) = self.ret_shape.take() else {
             return; // no return shape found
         };
-        let reified_ty = shape.clone().into();
         let syn::ReturnType::Type(_, box_ty) = output else {
             return self.err = Some(err!(output, "missing return type"));
         };
-        match box_ty.as_ref() {
-            syn::Type::Path(ty) => {
-                if ty.path.segments.len() == 2 && ty.path.segments[0].ident == format_ident!("Self")
-                {
-                    let ty_id = ty.path.segments[1].ident.clone();
-                    match self.assoc_types.insert(ty_id, reified_ty) {
-                        None => (),
-                        Some(syn::Type::ImplTrait(ty)) => {
-                            let is_reifiable = ty
-                                .bounds
-                                .into_iter()
-                                .flat_map(|bound| match bound {
-                                    syn::TypeParamBound::Trait(bound) => {
-                                        bound.path.get_ident().map(|id| {
-                                            self.shapes.family(id).any(|fam| fam.contains(&shape))
-                                        })
-                                    }
-                                    _ => unimplemented!(),
-                                })
-                                .any(|x| x);
-                            if !is_reifiable {
-                                self.reifiable = false;
-                                if self.verbose {
-                                    println!("shape not found: {}", shape)
-                                }
-                            }
-                        }
-                        Some(ty) => return self.err = Some(err!(ty, "")),
-                    };
+        match box_ty.as_mut() {
+            syn::Type::Path(ty)
+                if ty.path.segments.len() == 2
+                    && ty.path.segments[0].ident == format_ident!("Self") =>
+            {
+                let ty_id = ty.path.segments[1].ident.clone();
+                let Some(assoc_ty) = self.assoc_types.get(&ty_id).cloned() else {
+                    return;
+                };
+                if let Some(reified_ty) = self.reify_ret(&assoc_ty, ret) {
+                    self.assoc_types.insert(ty_id, reified_ty);
+                }
+            }
+            // inherent impls have no associated types to stash the shape in
+            ty @ (syn::Type::ImplTrait(_) | syn::Type::Tuple(_) | syn::Type::Array(_)) => {
+                if let Some(reified_ty) = self.reify_ret(ty, ret) {
+                    *ty = reified_ty
                 }
             }
             _ => (),
         }
     }
+    /// the return type `ty` with the shapes of the tail filled in
+    fn reify_ret(&mut self, ty: &syn::Type, ret: RetShape) -> Option<syn::Type> {
+        match (ty, ret) {
+            (ty, RetShape::Mv(shape)) => Some(self.reify_elem(ty, shape)),
+            (syn::Type::Tuple(tuple), RetShape::Tuple(shapes)) => {
+                if tuple.elems.len() != shapes.len() {
+                    let msg = format!("expected a tuple of {} shapes", shapes.len());
+                    self.err = Some(err!(tuple, msg));
+                    return None;
+                }
+                let elems = tuple
+                    .elems
+                    .iter()
+                    .zip(shapes)
+                    .map(|(elem, shape)| self.reify_elem(elem, shape))
+                    .collect_vec();
+                Some(parse_quote!((#(#elems,)*)))
+            }
+            (syn::Type::Array(array), RetShape::Array(shape, len)) => {
+                let is_len = matches!(
+                    &array.len,
+                    syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(n), .. })
+                        if n.base10_parse::<usize>().is_ok_and(|n| n == len)
+                );
+                if !is_len {
+                    let msg = format!("expected an array of {len} shapes");
+                    self.err = Some(err!(array.len, msg));
+                    return None;
+                }
+                let elem = self.reify_elem(&array.elem, shape);
+                let len = &array.len;
+                Some(parse_quote!([#elem; #len]))
+            }
+            _ => None,
+        }
+    }
+    /// `shape` in place of `impl Bound`, which must be in the family of one of the bounds
+    fn reify_elem(&mut self, ty: &syn::Type, shape: Shape) -> syn::Type {
+        let reified_ty: syn::Type = shape.clone().into();
+        match ty {
+            syn::Type::ImplTrait(ty) => self.check_shape_bounds(ty, shape),
+            // already reified by an earlier fn of the same impl
+            ty if *ty == reified_ty => (),
+            ty => {
+                let msg = format!(
+                    "conflicting shapes for this type, {shape} and {}",
+                    ty.to_token_stream()
+                );
+                self.err = Some(err!(ty, msg))
+            }
+        }
+        reified_ty
+    }
+    fn check_shape_bounds(&mut self, ty: &syn::TypeImplTrait, shape: Shape) {
+        let bounds = ty
+            .bounds
+            .iter()
+            .flat_map(|bound| match bound {
+                syn::TypeParamBound::Trait(bound) => bound.path.get_ident().cloned(),
+                _ => unimplemented!(),
+            })
+            .collect_vec();
+        let is_reifiable = bounds
+            .iter()
+            .any(|id| self.shapes.family(id).any(|fam| fam.contains(&shape)));
+        if !is_reifiable {
+            self.reifiable = false;
+            self.dropped.push(Dropped::new(
+                ty.span(),
+                &self.reified_types,
+                shape,
+                bounds,
+                self.shapes,
+                self.squares,
+            ));
+        }
+    }
 
     fn save_assoc_types(&mut self, items: &[syn::ImplItem]) {
         for item in items {
-            match item {
-                syn::ImplItem::Type(syn::ImplItemType { ident, ty, .. }) => {
-                    self.assoc_types.insert(ident.clone(), ty.clone());
-                }
-                _ => (),
+            if let syn::ImplItem::Type(syn::ImplItemType { ident, ty, .. }) = item {
+                self.assoc_types.insert(ident.clone(), ty.clone());
             }
         }
     }
     fn update_assoc_types(&mut self, it: &mut [syn::ImplItem]) {
         for item in it {
-            match item {
-                syn::ImplItem::Type(syn::ImplItemType { ident, ty, .. }) => {
-                    if let Some(assoc_ty) = self.assoc_types.remove(&ident) {
-                        *ty = assoc_ty
-                    }
-                }
-                _ => (),
+            if let syn::ImplItem::Type(syn::ImplItemType { ident, ty, .. }) = item
+                && let Some(assoc_ty) = self.assoc_types.remove(ident)
+            {
+                *ty = assoc_ty
             }
         }
     }
@@ -537,7 +1209,286 @@ impl Reifier<'_> {
             .iter()
             .find_map(|(t, s)| ty.eq(t).then_some(s))
     }
-    fn get_reified_arg(&self, ident: &syn::Ident) -> Option<&Shape> {
+    /// a reified fn of the module named like a builtin method that accepts the reified
+    /// operands, outside of its own body where the builtin is what it wraps
+    fn shadows_builtin(&self, method: &syn::Ident, ops: &[Option<Mvect<'a, CasExpr>>]) -> bool {
+        let callees = self.callees.get(method);
+        if callees.is_empty() || self.reifying.contains(method) {
+            return false;
+        }
+        let args: Option<Vec<Battery>> =
+            ops.iter().map(|mv| Some(mv.as_ref()?.battery())).collect();
+        args.is_some_and(|args| callees.iter().any(|c| self.fit(c, &args).is_some()))
+    }
+    fn is_callee(&self, func: &syn::Expr) -> bool {
+        match func {
+            syn::Expr::Path(func) => func
+                .path
+                .get_ident()
+                .is_some_and(|id| !self.callees.get(id).is_empty()),
+            _ => false,
+        }
+    }
+    /// evaluate another reified fn of this module on symbolic args, picking the variant
+    /// whose shape bounds accept the shapes of the args
+    fn inline_call(
+        &mut self,
+        ident: &syn::Ident,
+        args: Vec<Mvect<'a, CasExpr>>,
+        span: Span,
+    ) -> syn::Result<Mvect<'a, CasExpr>> {
+        if self.inline_depth >= MAX_INLINE_DEPTH {
+            return Err(err!(
+                ident,
+                format!("`{ident}` recurses too deeply to inline")
+            ));
+        }
+        let batteries = args.iter().map(Mvect::battery).collect_vec();
+        let callees = self.callees.get(ident);
+        let callee = callees
+            .iter()
+            .filter_map(|callee| Some((self.fit(callee, &batteries)?, callee)))
+            .min_by_key(|(zeros, _)| *zeros)
+            .map(|(_, callee)| callee)
+            .ok_or_else(|| {
+                let args = batteries.iter().join(", ");
+                let variants = callees.iter().map(|c| self.signature(ident, c)).join("; ");
+                err!(
+                    ident,
+                    format!("no reified `{ident}` accepts ({args}), the variants are {variants}")
+                )
+            })?;
+        let mut body = match callee.fn_.block.stmts.as_slice() {
+            [syn::Stmt::Expr(expr, None)] => expr.clone(),
+            [syn::Stmt::Macro(mac)] if mac.semi_token.is_none() => {
+                syn::Expr::Macro(syn::ExprMacro {
+                    attrs: mac.attrs.clone(),
+                    mac: mac.mac.clone(),
+                })
+            }
+            _ => {
+                let msg = format!("`{ident}` must be a single expression to be inlined");
+                return Err(err!(callee.fn_.block, msg));
+            }
+        };
+        // evaluate the body with the callee's args bound to the caller's values
+        let bindings = callee.inputs.iter().map(|(id, _)| id.clone()).zip(args);
+        let caller_args = std::mem::replace(&mut self.reified_args, bindings.collect());
+        let caller_err = self.err.take();
+        self.inline_depth += 1;
+        self.reifying.push(ident.clone());
+        self.visit_expr_mut(&mut body);
+        self.reifying.pop();
+        self.inline_depth -= 1;
+        self.reified_args = caller_args;
+        if let Some(e) = std::mem::replace(&mut self.err, caller_err) {
+            return Err(e);
+        }
+        let msg = format!("`{ident}` does not return a multivector");
+        self.mv_cas.take().ok_or(err!(span, msg))
+    }
+    /// how many blades are zero filled to pass `args` to `callee`, `None` when they don't fit.
+    /// `Field` inputs take scalars, and the args of a reified type need one of its shapes
+    /// to hold all of their blades
+    fn fit(&self, callee: &Callee, args: &[Battery]) -> Option<usize> {
+        if callee.inputs.len() != args.len() {
+            return None;
+        }
+        // a type used more than once must take the same shape each time
+        let mut bound: Vec<(&syn::Type, Battery)> = vec![];
+        for ((_, ty), arg) in callee.inputs.iter().zip(args) {
+            if is_field(ty) {
+                arg.is_scalar().then_some(())?;
+                continue;
+            }
+            match bound.iter_mut().find(|(t, _)| *t == ty) {
+                Some((_, blades)) => *blades = blades.union(arg),
+                None => bound.push((ty, arg.clone())),
+            }
+        }
+        bound.into_iter().try_fold(0, |zeros, (ty, blades)| {
+            // only reified args can be inlined
+            let cast = callee.binds.iter().find(|cast| &cast.ty == ty)?;
+            let shape = self
+                .shapes
+                .shapes(&cast.shape_id)
+                .map(|shape| shape.into_battery(self.squares))
+                .filter(|shape| blades.is_subset(shape))
+                .min_by_key(Battery::len)?;
+            Some(zeros + shape.len() - blades.len())
+        })
+    }
+    /// `ident(a: Line, t: Field) with Line one of Mv_e1_e2_e0`, for errors
+    fn signature(&self, ident: &syn::Ident, callee: &Callee) -> String {
+        let inputs = callee.inputs.iter().map(|(id, ty)| {
+            match callee.binds.iter().find(|cast| &cast.ty == ty) {
+                Some(cast) => format!("{id}: {}", cast.shape_id),
+                None => format!("{id}: {}", ty.to_token_stream()),
+            }
+        });
+        let families = callee
+            .binds
+            .iter()
+            .map(|cast| &cast.shape_id)
+            .unique()
+            .map(|id| format!("{id} one of {}", self.shapes.shapes(id).join(" or ")));
+        format!(
+            "{ident}({}) with {}",
+            inputs.format(", "),
+            families.format(", ")
+        )
+    }
+    /// the args of a fn and its body if it is a single expression, captured before the body is reified
+    fn variant_inputs(&self, impl_fn: &syn::ImplItemFn) -> Option<(Vec<Input>, Option<String>)> {
+        let body = match impl_fn.block.stmts.as_slice() {
+            [syn::Stmt::Expr(expr, None)] => Some(expr.to_token_stream()),
+            [syn::Stmt::Macro(mac)] if mac.semi_token.is_none() => Some(mac.mac.to_token_stream()),
+            _ => None,
+        };
+        let inputs = impl_fn.sig.inputs.iter().map(|arg| match arg {
+            syn::FnArg::Receiver(rec) if rec.mutability.is_none() => Some(Input {
+                ident: format_ident!("self"),
+                operand: Operand::Shape(self.rec_shape.clone()?),
+                by_ref: rec.reference.is_some(),
+            }),
+            syn::FnArg::Typed(pat_ty) => {
+                let syn::Pat::Ident(pat) = pat_ty.pat.as_ref() else {
+                    return None;
+                };
+                let operand = match self.get_reified_shape(&pat_ty.ty) {
+                    Some(shape) => Operand::Shape(shape.clone()),
+                    None if is_field(&pat_ty.ty) => Operand::Field,
+                    None if field_matrix(&pat_ty.ty).is_some() => {
+                        Operand::Matrix(self.squares.signature().len())
+                    }
+                    None => return None,
+                };
+                Some(Input {
+                    ident: pat.ident.clone(),
+                    operand,
+                    by_ref: false,
+                })
+            }
+            _ => None,
+        });
+        Some((
+            inputs.collect::<Option<_>>()?,
+            body.map(|body| body.to_string()),
+        ))
+    }
+    fn save_tail_mv(&mut self, mv: &Mvect<'a, CasExpr>) {
+        if self.capture {
+            self.tail_mv = Some(mv.clone())
+        }
+    }
+    fn save_variant(&mut self, sig: &syn::Signature, inputs: Vec<Input>, body: Option<String>) {
+        let tail_mv = self.tail_mv.take();
+        let ident = &sig.ident;
+        let span = ident.span();
+        let ret_ty = match &sig.output {
+            syn::ReturnType::Type(_, ty) => Some(ty.as_ref()),
+            syn::ReturnType::Default => None,
+        };
+        let (output, values) = match (self.scalar_ret, &self.ret_shape) {
+            (true, _) => {
+                let value = tail_mv.and_then(|mv| mv.try_into_scalar(span).ok());
+                (Operand::Field, value.map(|value| vec![value]))
+            }
+            (false, Some(RetShape::Mv(shape))) => {
+                let fields = tail_mv.and_then(|mv| mv.try_into_fields(self.canons, span).ok());
+                (
+                    Operand::Shape(shape.clone()),
+                    fields.map(|(_, values)| values),
+                )
+            }
+            // the rest have no shader or C counterpart, only the selftests check them
+            (false, Some(RetShape::Tuple(shapes))) => (Operand::Tuple(shapes.clone()), None),
+            (false, Some(RetShape::Array(shape, len))) => {
+                (Operand::Array(shape.clone(), *len), None)
+            }
+            (false, None) if ret_ty.is_some_and(is_bool) => (Operand::Bool, None),
+            (false, None) if ret_ty.and_then(field_matrix).is_some() => {
+                (Operand::Matrix(self.squares.signature().len()), None)
+            }
+            (false, None) => return,
+        };
+        // runtime meets, joins and factorizations are only bound in the rust fn
+        let values = values.filter(|_| self.subspaces.is_empty());
+        self.pending.push(Variant {
+            ident: ident.clone(),
+            path: None,
+            trait_: None,
+            inputs,
+            output,
+            body,
+            values,
+        })
+    }
+    /// the fns are called through the reified impl
+    fn save_variant_paths(&mut self, impl_: &syn::ItemImpl) {
+        if !impl_.generics.params.is_empty() {
+            return self.pending.clear();
+        }
+        let self_ty = &impl_.self_ty;
+        for test in self.pending.iter_mut().filter(|test| test.path.is_none()) {
+            let ident = &test.ident;
+            test.path = Some(match &impl_.trait_ {
+                Some((_, trait_, _)) => parse_quote!(<#self_ty as #trait_>::#ident),
+                None => parse_quote!(<#self_ty>::#ident),
+            });
+            test.trait_ = impl_.trait_.as_ref().map(|(_, trait_, _)| {
+                let last = trait_.segments.last().unwrap();
+                last.ident.clone()
+            });
+        }
+    }
+    /// `Basis::e02` or `e02`, as in `mv[Basis::e02]` and `mv.get(Basis::e02)`
+    fn basis_blade(&self, expr: &syn::Expr) -> syn::Result<Blade> {
+        let syn::Expr::Path(path) = expr else {
+            return Err(err!(expr, "expected a `Basis` blade"));
+        };
+        let ident = &path.path.segments.last().unwrap().ident;
+        let blank: Blank = ident.clone().try_into()?;
+        Ok(Blunt::from(blank).hone(self.squares))
+    }
+    /// `(A { .. }, B { .. })`, saving the shapes for the return type
+    fn tuple_expr(&mut self, parts: Vec<Mvect<'a, CasExpr>>, span: Span) -> syn::Result<syn::Expr> {
+        let shapes = parts
+            .iter()
+            .map(|part| part.try_into_shape(self.canons, span))
+            .collect::<syn::Result<_>>()?;
+        self.ret_shape = Some(RetShape::Tuple(shapes));
+        let parts = parts
+            .into_iter()
+            .map(|part| part.try_into_expr(self.canons, span))
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(parse_quote!((#(#parts,)*)))
+    }
+    /// `[A { .. }, A { .. }]`, saving the shape for the return type
+    fn array_expr(&mut self, parts: Vec<Mvect<'a, CasExpr>>, span: Span) -> syn::Result<syn::Expr> {
+        let shape = match parts.first() {
+            Some(part) => part.try_into_shape(self.canons, span)?,
+            None => return Err(err!(span, "nothing to return")),
+        };
+        self.ret_shape = Some(RetShape::Array(shape, parts.len()));
+        let parts = parts
+            .into_iter()
+            .map(|part| part.try_into_expr(self.canons, span))
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(parse_quote!([#(#parts),*]))
+    }
+    /// a `[[Field; DIM]; DIM]` arg of the fn
+    fn matrix_arg(&self, expr: &syn::Expr) -> syn::Result<&Vec<Vec<CasExpr>>> {
+        let m = match expr {
+            syn::Expr::Path(path) => self
+                .matrices
+                .iter()
+                .find_map(|(ident, m)| path.path.is_ident(ident).then_some(m)),
+            _ => None,
+        };
+        m.ok_or_else(|| err!(expr, "expected a `[[Field; DIM]; DIM]` argument"))
+    }
+    fn get_reified_arg(&self, ident: &syn::Ident) -> Option<&Mvect<'a, CasExpr>> {
         self.reified_args
             .iter()
             .find_map(|(id, ty)| (id == ident).then_some(ty))
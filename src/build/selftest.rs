@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use reefer_core::{
+    algebra::Algebra,
+    eval::eval_parts_str,
+    geometry::{Shape, SquareMap},
+};
+use syn::parse_quote;
+
+use super::variant::{Operand, Variant};
+
+impl Variant {
+    /// the evaluator has to understand the body, otherwise there is nothing to check against
+    /// and the reason is reported instead
+    fn dry_run(&self, alg: &Algebra) -> Result<(), String> {
+        let Some(body) = &self.body else {
+            return Err("the body isn't a single expression".into());
+        };
+        let mut env = vec![];
+        let mut matrices = vec![];
+        for input in &self.inputs {
+            let name = input.ident.to_string();
+            match &input.operand {
+                Operand::Matrix(dim) => matrices.push((name, vec![vec![1.0; *dim]; *dim])),
+                operand => {
+                    let names = operand.parts().concat();
+                    let mv = alg
+                        .mv(names.iter().map(|name| (name.as_str(), 1.0)))
+                        .map_err(|e| e.to_string())?;
+                    env.push((name, mv))
+                }
+            }
+        }
+        let env = env
+            .iter()
+            .map(|(name, mv)| (name.as_str(), mv.clone()))
+            .collect::<Vec<_>>();
+        let matrices = matrices
+            .iter()
+            .map(|(name, m)| (name.as_str(), m.clone()))
+            .collect::<Vec<_>>();
+        for blade in self.output.parts().concat() {
+            alg.blade(&blade).map_err(|e| e.to_string())?;
+        }
+        eval_parts_str(alg, body, &env, &matrices)
+            .map_err(|e| format!("the evaluator can't run it: {e}"))?;
+        Ok(())
+    }
+    /// the fn and its arg types, to tell the tests of its variants apart
+    fn label(&self) -> String {
+        let types = self.inputs.iter().map(|input| input.operand.type_name());
+        format!("{}({})", self.ident, types.format(", "))
+    }
+    /// an ignored test standing in for a variant that can't be checked
+    fn to_skipped_test(&self, name: syn::Ident, reason: String) -> syn::ItemFn {
+        let reason = format!("{} isn't checked, {reason}", self.label());
+        parse_quote! {
+            #[test]
+            #[ignore = #reason]
+            fn #name() {}
+        }
+    }
+    fn to_test(&self, name: syn::Ident, algebra: &syn::Expr) -> syn::ItemFn {
+        let path = self
+            .path
+            .as_ref()
+            .expect("path is set once the impl is visited");
+        let draws = self.inputs.iter().enumerate().map(|(i, input)| {
+            let values = format_ident!("arg_{i}");
+            let n = input.operand.parts().concat().len();
+            quote!(let #values: [f64; #n] = rng.array().map(|x: f64| x as Field as f64);)
+        });
+        let args = self.inputs.iter().enumerate().map(|(i, input)| {
+            let values = format_ident!("arg_{i}");
+            let reference = input.by_ref.then(|| quote!(&));
+            match &input.operand {
+                Operand::Field => quote!(#values[0] as Field),
+                Operand::Matrix(dim) => quote! {
+                    ::std::array::from_fn(|i| ::std::array::from_fn(|j| #values[i * #dim + j] as Field))
+                },
+                Operand::Shape(shape) => {
+                    let ty: syn::Type = shape.clone().into();
+                    let fields = shape.clone().into_iter().enumerate().map(|(j, blank)| {
+                        let field = format_ident!("{blank}");
+                        quote!(#field: #values[#j] as Field)
+                    });
+                    quote!(#reference #ty { #(#fields),* })
+                }
+                operand => unreachable!("{operand:?} is only returned"),
+            }
+        });
+        let env = self.inputs.iter().enumerate().filter_map(|(i, input)| {
+            let values = format_ident!("arg_{i}");
+            let name = input.ident.to_string();
+            let blades = match &input.operand {
+                Operand::Matrix(_) => return None,
+                operand => operand.parts().concat().into_iter().enumerate(),
+            };
+            let pairs = blades.map(|(j, blade)| quote!((#blade, #values[#j])));
+            Some(quote!((#name, alg.mv([#(#pairs),*]).unwrap())))
+        });
+        let matrices = self.inputs.iter().enumerate().filter_map(|(i, input)| {
+            let values = format_ident!("arg_{i}");
+            let name = input.ident.to_string();
+            let Operand::Matrix(dim) = input.operand else {
+                return None;
+            };
+            Some(quote!((#name, #values.chunks(#dim).map(<[f64]>::to_vec).collect())))
+        });
+        // the fields of a shaped part of `out`
+        let fields = |shape: &Shape, part: TokenStream| {
+            let pairs = shape.clone().into_iter().map(|blank| {
+                let field = format_ident!("{blank}");
+                let blade = blank.to_string();
+                quote!((#blade, #part.#field as f64))
+            });
+            quote!(&[#(#pairs),*])
+        };
+        let actual = match &self.output {
+            Operand::Shape(shape) => vec![fields(shape, quote!(out))],
+            Operand::Field => vec![quote!(&[("scalar", out as f64)])],
+            Operand::Bool => vec![quote!(&[("scalar", if out { 1.0 } else { 0.0 })])],
+            Operand::Matrix(dim) => (0..*dim)
+                .cartesian_product(0..*dim)
+                .map(|(i, j)| quote!(&[("scalar", out[#i][#j] as f64)]))
+                .collect(),
+            Operand::Tuple(shapes) => shapes
+                .iter()
+                .enumerate()
+                .map(|(k, shape)| {
+                    let k = syn::Index::from(k);
+                    fields(shape, quote!(out.#k))
+                })
+                .collect(),
+            Operand::Array(shape, len) => {
+                (0..*len).map(|k| fields(shape, quote!(out[#k]))).collect()
+            }
+        };
+        let body = self
+            .body
+            .as_ref()
+            .expect("only variants passing the dry run are tested");
+        let label = format!("{} `{body}`", self.label());
+        parse_quote! {
+            #[test]
+            fn #name() {
+                let alg = #algebra;
+                let mut rng = ::reefer_core::check::Rng::default();
+                for _ in 0..::reefer_core::check::ROUNDS {
+                    #(#draws)*
+                    let out = #path(#(#args),*);
+                    let expected = ::reefer_core::eval::eval_parts_str(
+                        &alg, #body, &[#(#env),*], &[#(#matrices),*]
+                    ).unwrap();
+                    ::reefer_core::check::assert_parts_close(&alg, &expected, &[#(#actual),*], #label);
+                }
+            }
+        }
+    }
+}
+
+/// a test module checking every reified variant the evaluator understands, with an ignored
+/// test saying why for the others
+pub fn selftest_mod(squares: &SquareMap, tests: &[Variant]) -> Option<syn::Item> {
+    let alg = Algebra::from(squares.clone());
+    let pairs = squares.signature().into_iter().map(|(axis, square)| {
+        let axis = axis.to_string();
+        let square = i8::from(square);
+        quote!((#axis, #square))
+    });
+    let algebra: syn::Expr =
+        parse_quote!(::reefer_core::algebra::Algebra::new([#(#pairs),*]).unwrap());
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut checked = false;
+    let fns = tests
+        .iter()
+        .map(|test| {
+            let count = counts.entry(test.ident.to_string()).or_default();
+            let name = format_ident!("{}_{count}", test.ident);
+            *count += 1;
+            match test.dry_run(&alg) {
+                Ok(()) => {
+                    checked = true;
+                    test.to_test(name, &algebra)
+                }
+                Err(reason) => test.to_skipped_test(name, reason),
+            }
+        })
+        .collect::<Vec<_>>();
+    if fns.is_empty() {
+        return None;
+    }
+    // ignored tests don't use the module
+    let uses = checked.then(|| {
+        quote!(
+            use super::*;
+        )
+    });
+    Some(parse_quote! {
+        #[cfg(test)]
+        #[allow(non_snake_case, clippy::unnecessary_cast)]
+        mod reefer_selftest {
+            #uses
+            #(#fns)*
+        }
+    })
+}
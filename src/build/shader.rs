@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use quote::format_ident;
+use reefer_core::{cas::CasExpr, geometry::ShapeMap, shader::Lang};
+use syn::parse_quote;
+
+use super::variant::{Names, Operand, Variant};
+
+/// where generated files go if anywhere, `$REEFER_DUMP_DIR` with relative paths inside
+/// `$CARGO_TARGET_DIR` when that's set
+pub fn dump_dir() -> Option<PathBuf> {
+    let dir = PathBuf::from(std::env::var_os("REEFER_DUMP_DIR")?);
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(target) if dir.is_relative() => Some(PathBuf::from(target).join(dir)),
+        _ => Some(dir),
+    }
+}
+
+/// writes `src` to `{dump_dir}/{mod}.{ext}`, best effort since the source is also a const
+/// of the module and a read-only dir shouldn't fail the build
+pub fn write_dump(mod_ident: &syn::Ident, ext: &str, src: &str) {
+    let Some(dir) = dump_dir() else {
+        return;
+    };
+    let path = dir.join(format!("{mod_ident}.{ext}"));
+    let _ = std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(path, src));
+}
+
+/// shader source with a struct for every shape and a fn for every reified variant
+pub fn shader_source(
+    lang: Lang,
+    mod_ident: &syn::Ident,
+    shapes: &ShapeMap,
+    consts: &[(syn::Ident, CasExpr)],
+    variants: &[Variant],
+) -> String {
+    let mut src = format!("// generated by reefer from `mod {mod_ident}`, do not edit\n");
+    // shaders have no zero sized structs
+    let shapes = shapes.all_shapes().into_iter().filter(|s| !s.is_empty());
+    for shape in shapes {
+        src.push('\n');
+        src.push_str(&lang.struct_(&shape));
+    }
+    if !consts.is_empty() {
+        src.push('\n');
+    }
+    for (ident, value) in consts {
+        src.push_str(&lang.const_(&ident.to_string(), value));
+    }
+    let mut names = Names::default();
+    for variant in variants {
+        let Some(values) = &variant.values else {
+            continue;
+        };
+        let ty = |operand: &Operand| match operand {
+            Operand::Shape(shape) if shape.is_empty() => None,
+            Operand::Shape(shape) => Some(shape.to_string()),
+            Operand::Field => Some(lang.float().to_string()),
+            _ => None,
+        };
+        let Some(params) = variant
+            .inputs
+            .iter()
+            .map(|input| Some((input.ident.to_string(), ty(&input.operand)?)))
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        let Some(ret) = ty(&variant.output) else {
+            continue;
+        };
+        let value = match &variant.output {
+            Operand::Shape(shape) => lang.construct(shape, values),
+            Operand::Field => values[0].to_shader(lang),
+            _ => continue,
+        };
+        let name = names.unique(variant.name());
+        src.push('\n');
+        src.push_str(&lang.fn_(&name, &params, &ret, &value));
+    }
+    src
+}
+
+/// writes the shader to `{dump_dir}/{mod}.{ext}` and exposes the source as a const of the module
+pub fn shader_item(
+    lang: Lang,
+    mod_ident: &syn::Ident,
+    shapes: &ShapeMap,
+    consts: &[(syn::Ident, CasExpr)],
+    variants: &[Variant],
+) -> syn::Item {
+    let src = shader_source(lang, mod_ident, shapes, consts, variants);
+    write_dump(mod_ident, lang.ext(), &src);
+    let const_ident = format_ident!("{}", lang.ext().to_uppercase());
+    let doc = format!(
+        " the shapes and reified fns of this module in {}",
+        lang.ext().to_uppercase()
+    );
+    parse_quote! {
+        #[doc = #doc]
+        pub const #const_ident: &str = #src;
+    }
+}
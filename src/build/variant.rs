@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use reefer_core::{cas::CasExpr, geometry::Shape};
+
+/// the type of an arg or return value of a reified fn
+#[derive(Debug, Clone)]
+pub enum Operand {
+    Shape(Shape),
+    Field,
+    /// the value of a comparison, which the evaluator makes 1 or 0
+    Bool,
+    /// `[[Field; DIM]; DIM]`, as taken by `outermorphism` and returned by `to_matrix`
+    Matrix(usize),
+    /// the parts of a `split()`
+    Tuple(Vec<Shape>),
+    /// the factors of a `factorize()`
+    Array(Shape, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub ident: syn::Ident,
+    pub operand: Operand,
+    pub by_ref: bool,
+}
+
+/// a reified fn variant, kept for the selftests and shaders generated alongside the impls
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub ident: syn::Ident,
+    pub path: Option<syn::ExprPath>,
+    /// the trait the fn is implemented for, `None` in inherent impls
+    pub trait_: Option<syn::Ident>,
+    pub inputs: Vec<Input>,
+    pub output: Operand,
+    /// source of a single expression body before reification
+    pub body: Option<String>,
+    /// the reified value of each output field, `None` when they need runtime bindings
+    pub values: Option<Vec<CasExpr>>,
+}
+
+impl Operand {
+    /// the blades of each part, the entries of a matrix are scalars
+    pub fn parts(&self) -> Vec<Vec<String>> {
+        let blades = |shape: &Shape| {
+            shape
+                .clone()
+                .into_iter()
+                .map(|b| b.to_string())
+                .collect_vec()
+        };
+        let scalar = vec!["scalar".to_string()];
+        match self {
+            Operand::Shape(shape) => vec![blades(shape)],
+            Operand::Field | Operand::Bool => vec![scalar],
+            Operand::Matrix(dim) => vec![scalar; dim * dim],
+            Operand::Tuple(shapes) => shapes.iter().map(blades).collect(),
+            Operand::Array(shape, len) => vec![blades(shape); *len],
+        }
+    }
+    /// the type as it appears in names and labels
+    pub fn type_name(&self) -> String {
+        match self {
+            Operand::Shape(shape) => shape.to_string(),
+            Operand::Field => "Field".into(),
+            Operand::Bool => "bool".into(),
+            Operand::Matrix(_) => "Matrix".into(),
+            Operand::Tuple(shapes) => shapes.iter().join("_"),
+            Operand::Array(shape, len) => format!("{shape}_{len}"),
+        }
+    }
+}
+
+impl Variant {
+    /// the method and its arg shapes, generated code without overloading needs both
+    pub fn name(&self) -> String {
+        let types = self.inputs.iter().map(|input| input.operand.type_name());
+        std::iter::once(self.ident.to_string())
+            .chain(types)
+            .join("_")
+    }
+    /// `name` qualified by the trait, unless the trait is named after the method like
+    /// operators and reified free fns
+    pub fn qualified_name(&self) -> String {
+        let method = self.ident.to_string().replace('_', "");
+        match &self.trait_ {
+            Some(trait_) if !trait_.to_string().eq_ignore_ascii_case(&method) => {
+                format!("{trait_}_{}", self.name())
+            }
+            _ => self.name(),
+        }
+    }
+}
+
+/// numbers repeated names, the first one is left as is
+#[derive(Debug, Default)]
+pub struct Names(HashMap<String, usize>);
+
+impl Names {
+    pub fn unique(&mut self, name: String) -> String {
+        let count = self.0.entry(name.clone()).or_default();
+        *count += 1;
+        match *count {
+            1 => name,
+            n => format!("{name}_{}", n - 1),
+        }
+    }
+}
@@ -2,24 +2,9 @@ use proc_macro::TokenStream;
 use quote::ToTokens;
 use syn::{parse_macro_input, punctuated::Punctuated};
 
-mod build;
-mod cas;
-mod geometry;
-mod sort;
-mod traits;
+use reefer_core::err;
 
-/// helper for building syn::Error errors
-macro_rules! err {
-    ($expr:expr, $msg:expr) => {{
-        #[allow(unused_imports)]
-        use syn::spanned::Spanned;
-        syn::Error::new(($expr).span(), $msg)
-    }};
-    ($msg:expr) => {
-        syn::Error::new(proc_macro2::Span::call_site(), $msg)
-    };
-}
-pub(crate) use err;
+mod build;
 
 /// macro for naming shape types
 ///     Mv!(e0, e1, e2) -> Mv_e0_e1_e2
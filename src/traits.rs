@@ -1,4 +0,0 @@
-pub trait Squareroot {
-    type Output;
-    fn sqrt(self) -> Self::Output;
-}
@@ -0,0 +1,322 @@
+//! Lengyel's conventions, points are vectors with the weight in e4 and motors act through the antisandwich
+pub trait Transform<Rhs> {
+    type Output;
+    fn transform(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Compose<Rhs> {
+    type Output;
+    fn compose(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Meet<Rhs> {
+    type Output;
+    fn meet(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Antidot<Rhs> {
+    type Output;
+    fn antidot(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Distance<Rhs> {
+    fn distance(self, rhs: Rhs) -> Field;
+}
+pub trait Unitize {
+    type Output;
+    fn unitized(self) -> Self::Output;
+}
+pub trait BulkDual {
+    type Output;
+    fn bulk_dual(self) -> Self::Output;
+}
+pub trait WeightDual {
+    type Output;
+    fn weight_dual(self) -> Self::Output;
+}
+
+type Field = f64;
+
+pub trait Pow {
+    fn pow(self, n: Self) -> Self;
+}
+impl Pow for Field {
+    fn pow(self, n: Self) -> Self {
+        self.powf(n)
+    }
+}
+
+#[reefer::algebraic(selftest)]
+#[allow(dead_code)]
+mod pga3d {
+    use super::*;
+
+    square!(e1, 1);
+    square!(e2, 1);
+    square!(e3, 1);
+    square!(e4, 0);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Point, Mv<e1, e2, e3, e4>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Line, Mv<e41, e42, e43, e23, e31, e12>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Plane, Mv<e423, e431, e412, e321>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Motor, Mv<e41, e42, e43, e1234, e23, e31, e12, scalar>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Antiscalar, Mv<e1234>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(OriginPlane, Mv<e423, e431, e412>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Horizon, Mv<e321>);
+
+    #[reify(Motor as M)]
+    #[reify(Point as P)]
+    impl Transform<P> for M {
+        type Output = impl Point;
+        fn transform(self, point: P) -> Self::Output {
+            self.antisandwich(point).simplify()
+        }
+    }
+
+    #[reify(Motor as A)]
+    #[reify(Motor as B)]
+    impl Compose<B> for A {
+        type Output = impl Motor;
+        fn compose(self, rhs: B) -> Self::Output {
+            self.antimul(rhs)
+        }
+    }
+
+    #[reify(Plane as A)]
+    #[reify(Plane as B)]
+    impl Meet<B> for A {
+        type Output = impl Line;
+        fn meet(self, rhs: B) -> Self::Output {
+            self.antiwedge(rhs)
+        }
+    }
+
+    #[reify(Point as A)]
+    #[reify(Point as B)]
+    impl Antidot<B> for A {
+        type Output = impl Antiscalar;
+        fn antidot(self, rhs: B) -> Self::Output {
+            self.antidot(rhs)
+        }
+    }
+
+    #[reify(Point as A)]
+    #[reify(Point as B)]
+    impl Distance<B> for A {
+        fn distance(self, rhs: B) -> Field {
+            (self.unitize() ^ rhs.unitize()).weight_norm().scalar
+        }
+    }
+
+    #[reify(Point as P)]
+    impl Unitize for P {
+        type Output = impl Point;
+        fn unitized(self) -> Self::Output {
+            self.unitize()
+        }
+    }
+
+    #[reify(Point as P)]
+    impl BulkDual for P {
+        type Output = impl OriginPlane;
+        fn bulk_dual(self) -> Self::Output {
+            self.bulk_right_complement()
+        }
+    }
+
+    #[reify(Point as P)]
+    impl WeightDual for P {
+        type Output = impl Horizon;
+        fn weight_dual(self) -> Self::Output {
+            self.weight_right_complement()
+        }
+    }
+}
+
+use pga3d::{
+    Mv_e1_e2_e3_e4 as Point, Mv_e41_e42_e43_e1234_e23_e31_e12_scalar as Motor,
+    Mv_e423_e431_e412_e321 as Plane,
+};
+
+fn point(x: f64, y: f64, z: f64) -> Point {
+    Point {
+        e1: x,
+        e2: y,
+        e3: z,
+        e4: 1.0,
+    }
+}
+
+fn assert_near(a: Point, b: Point) {
+    let d = [a.e1 - b.e1, a.e2 - b.e2, a.e3 - b.e3, a.e4 - b.e4];
+    assert!(d.iter().all(|d| d.abs() < 1e-12), "{a:?} != {b:?}");
+}
+
+/// `(tx, ty, tz) / 2` in the bulk and the antiscalar weight
+fn translation(x: f64, y: f64, z: f64) -> Motor {
+    Motor {
+        e23: x / 2.0,
+        e31: y / 2.0,
+        e12: z / 2.0,
+        e1234: 1.0,
+        ..zero()
+    }
+}
+
+/// about the z axis through the origin
+fn rotation_z(angle: f64) -> Motor {
+    Motor {
+        e43: (angle / 2.0).sin(),
+        e1234: (angle / 2.0).cos(),
+        ..zero()
+    }
+}
+
+fn zero() -> Motor {
+    Motor {
+        e41: 0.0,
+        e42: 0.0,
+        e43: 0.0,
+        e1234: 0.0,
+        e23: 0.0,
+        e31: 0.0,
+        e12: 0.0,
+        scalar: 0.0,
+    }
+}
+
+#[test]
+fn test_translation() {
+    let moved = translation(1.0, -2.0, 3.0).transform(point(0.5, 0.5, 0.5));
+    assert_near(moved, point(1.5, -1.5, 3.5));
+}
+
+#[test]
+fn test_rotation() {
+    let turned = rotation_z(std::f64::consts::FRAC_PI_2).transform(point(1.0, 0.0, 2.0));
+    assert_near(turned, point(0.0, 1.0, 2.0));
+}
+
+/// the motor to matrix conversion for unitized motors
+fn matrix_transform(q: Motor, p: Point) -> Point {
+    let (rx, ry, rz, rw) = (q.e41, q.e42, q.e43, q.e1234);
+    let (ux, uy, uz, uw) = (q.e23, q.e31, q.e12, q.scalar);
+    let m = [
+        [
+            1.0 - 2.0 * (ry * ry + rz * rz),
+            2.0 * (rx * ry - rw * rz),
+            2.0 * (rz * rx + rw * ry),
+            2.0 * (ry * uz - rz * uy + rw * ux - rx * uw),
+        ],
+        [
+            2.0 * (rx * ry + rw * rz),
+            1.0 - 2.0 * (rz * rz + rx * rx),
+            2.0 * (ry * rz - rw * rx),
+            2.0 * (rz * ux - rx * uz + rw * uy - ry * uw),
+        ],
+        [
+            2.0 * (rz * rx - rw * ry),
+            2.0 * (ry * rz + rw * rx),
+            1.0 - 2.0 * (rx * rx + ry * ry),
+            2.0 * (rx * uy - ry * ux + rw * uz - rz * uw),
+        ],
+    ];
+    let v = [p.e1, p.e2, p.e3, p.e4];
+    let row = |r: [f64; 4]| r.iter().zip(v).map(|(a, b)| a * b).sum();
+    Point {
+        e1: row(m[0]),
+        e2: row(m[1]),
+        e3: row(m[2]),
+        e4: p.e4,
+    }
+}
+
+#[test]
+fn test_motor_formula() {
+    // a screw, unitized since it is composed of unitized motors
+    let rotation = Motor {
+        e41: 0.36,
+        e42: 0.48,
+        e43: 0.0,
+        e1234: 0.8,
+        ..zero()
+    };
+    let q = translation(0.3, -1.2, 2.0)
+        .compose(rotation)
+        .compose(rotation_z(0.7));
+    for p in [
+        point(1.0, 2.0, 3.0),
+        point(-0.5, 0.25, 4.0),
+        Point {
+            e1: 1.0,
+            e2: 0.0,
+            e3: -1.0,
+            e4: 0.0,
+        },
+    ] {
+        assert_near(q.transform(p), matrix_transform(q, p));
+    }
+}
+
+#[test]
+fn test_antiwedge_and_antidot() {
+    // the planes x = 1 and y = 2 meet in the line through (1, 2, 0) along -z,
+    // f ∨ g has direction g × f and moment f_xyz * g_w - f_w * g_xyz
+    let x1 = Plane {
+        e423: 1.0,
+        e431: 0.0,
+        e412: 0.0,
+        e321: -1.0,
+    };
+    let y2 = Plane {
+        e423: 0.0,
+        e431: 1.0,
+        e412: 0.0,
+        e321: -2.0,
+    };
+    let line = x1.meet(y2);
+    assert_eq!((line.e41, line.e42, line.e43), (0.0, 0.0, -1.0));
+    assert_eq!((line.e23, line.e31, line.e12), (-2.0, 1.0, 0.0));
+    // only the weights survive the antidot, e4 squares to zero
+    let a = Point {
+        e1: 3.0,
+        e2: 4.0,
+        e3: 5.0,
+        e4: 2.0,
+    };
+    let b = Point {
+        e1: 1.0,
+        e2: 1.0,
+        e3: 1.0,
+        e4: 3.0,
+    };
+    assert_eq!(a.antidot(b).e1234, 6.0);
+}
+
+#[test]
+fn test_duals() {
+    let p = Point {
+        e1: 1.0,
+        e2: 2.0,
+        e3: 3.0,
+        e4: 4.0,
+    };
+    let bulk = p.bulk_dual();
+    assert_eq!([bulk.e423, bulk.e431, bulk.e412], [1.0, 2.0, 3.0]);
+    assert_eq!(p.weight_dual().e321, 4.0);
+}
+
+#[test]
+fn test_distance() {
+    let a = Point {
+        e1: 2.0,
+        e2: 4.0,
+        e3: 6.0,
+        e4: 2.0,
+    };
+    assert_near(a.unitized(), point(1.0, 2.0, 3.0));
+    assert!((a.distance(point(4.0, 6.0, 3.0)) - 5.0).abs() < 1e-12);
+}
@@ -0,0 +1,65 @@
+#[reefer::algebraic(repr_c, bytemuck)]
+#[allow(dead_code)]
+mod pga2d {
+    use std::ops::BitXor;
+
+    type Field = f32;
+
+    square!(e0, 0);
+    square!(e1, 1);
+    square!(e2, 1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Line, Mv<e1, e2, e0>);
+    #[derive(Debug, PartialEq)]
+    shape!(Point, Mv<e01, e20, e12>);
+
+    #[reify(Line as A)]
+    #[reify(Line as B)]
+    impl BitXor<B> for A {
+        type Output = impl Point;
+        /// meet two lines into a point
+        fn bitxor(self, line: B) -> Self::Output {
+            self ^ line
+        }
+    }
+}
+
+use pga2d::{Mv_e1_e2_e0 as Line, Mv_e01_e20_e12 as Point};
+
+#[test]
+fn test_array_conversions() {
+    assert_eq!(Line::LEN, 3);
+    let line = Line::from([1.0, 2.0, 3.0]);
+    assert_eq!(
+        line,
+        Line {
+            e1: 1.0,
+            e2: 2.0,
+            e0: 3.0
+        }
+    );
+    assert_eq!(<[f32; Line::LEN]>::from(line), [1.0, 2.0, 3.0]);
+    let point: [f32; 3] = (line ^ Line::from([0.0, 1.0, -2.0])).into();
+    assert_eq!(Point::from(point), line ^ Line::from([0.0, 1.0, -2.0]));
+}
+
+#[test]
+fn test_as_array() {
+    let mut line = Line::from([1.0, 2.0, 3.0]);
+    assert_eq!(line.as_array(), &[1.0, 2.0, 3.0]);
+    line.as_array_mut()[2] = -1.0;
+    assert_eq!(line.e0, -1.0);
+}
+
+#[test]
+fn test_pod() {
+    let lines = [Line::from([1.0, 2.0, 3.0]), Line::from([4.0, 5.0, 6.0])];
+    let floats: &[f32] = bytemuck::cast_slice(&lines);
+    assert_eq!(floats, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    // `Copy` is derived where the shape didn't already
+    let point: Point = bytemuck::Zeroable::zeroed();
+    let copy = point;
+    assert_eq!(point, copy);
+    assert_eq!(bytemuck::bytes_of(&point).len(), 12);
+}
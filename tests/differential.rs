@@ -0,0 +1,973 @@
+//! every reified op is checked against the numeric evaluator by the generated `reefer_selftest` tests
+
+pub trait Dot<Rhs> {
+    type Output;
+    fn dot(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Commutate<Rhs> {
+    type Output;
+    fn commutate(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Anticomm<Rhs> {
+    type Output;
+    fn anticomm(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Involute {
+    type Output;
+    fn aut(self) -> Self::Output;
+    fn rev(self) -> Self::Output;
+    fn conj(self) -> Self::Output;
+}
+pub trait Flip {
+    type Output;
+    fn tilde(self) -> Self::Output;
+    fn clifford(self) -> Self::Output;
+}
+pub trait Parts {
+    type Even;
+    type Odd;
+    type Middle;
+    fn even(self) -> Self::Even;
+    fn odd(self) -> Self::Odd;
+    fn middle(self) -> Self::Middle;
+}
+pub trait Join<Rhs> {
+    type Output;
+    fn join(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Meet<Rhs> {
+    type Output;
+    fn meet(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Duality {
+    type Output;
+    fn dual(self) -> Self::Output;
+    fn undual(self) -> Self::Output;
+}
+pub trait Square {
+    type Output;
+    fn square(self) -> Self::Output;
+}
+pub trait Part<Rhs> {
+    type Output;
+    fn part(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Scale {
+    type Output;
+    fn scale(self, k: Field) -> Self::Output;
+}
+pub trait Norm {
+    type Output;
+    fn norm(&self) -> Field;
+    fn normed(self) -> Self::Output;
+}
+
+pub trait Component<Rhs> {
+    fn component(self, rhs: Rhs) -> Field;
+}
+/// the evaluator makes the comparisons 1 or 0
+pub trait Compare<Rhs> {
+    fn equals(self, rhs: Rhs) -> bool;
+    fn differs(self, rhs: Rhs) -> bool;
+    fn below(self, rhs: Rhs) -> bool;
+    fn at_most(self, rhs: Rhs) -> bool;
+    fn above(self, rhs: Rhs) -> bool;
+    fn at_least(self, rhs: Rhs) -> bool;
+}
+pub trait Projection<Rhs> {
+    type Output;
+    fn project(self, rhs: Rhs) -> Self::Output;
+    fn reject(self, rhs: Rhs) -> Self::Output;
+    fn reflect(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Antimul<Rhs> {
+    type Output;
+    fn antimul(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Antiwedge<Rhs> {
+    type Output;
+    fn antiwedge(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Antidot<Rhs> {
+    type Output;
+    fn antidot(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Antisandwich<Rhs> {
+    type Output;
+    fn antisandwich(self, rhs: Rhs) -> Self::Output;
+}
+pub trait Complement {
+    type Output;
+    type Bulk;
+    type Weight;
+    fn right_complement(self) -> Self::Output;
+    fn left_complement(self) -> Self::Output;
+    fn bulk_right_complement(self) -> Self::Bulk;
+    fn bulk_left_complement(self) -> Self::Bulk;
+    fn weight_right_complement(self) -> Self::Weight;
+    fn weight_left_complement(self) -> Self::Weight;
+    fn hodge(self) -> Self::Bulk;
+}
+pub trait Weights {
+    type Output;
+    type Bulk;
+    type Weight;
+    fn bulk(self) -> Self::Bulk;
+    fn weight(self) -> Self::Weight;
+    fn bulk_norm(self) -> Field;
+    fn weight_norm(self) -> Field;
+    fn unitize(self) -> Self::Output;
+}
+pub trait Split {
+    type Output;
+    fn split(self) -> Self::Output;
+}
+pub trait Factors {
+    type Output;
+    fn factors(self) -> Self::Output;
+}
+
+type Field = f32;
+
+pub trait Pow {
+    fn pow(self, n: Self) -> Self;
+}
+impl Pow for Field {
+    fn pow(self, n: Self) -> Self {
+        self.powf(n)
+    }
+}
+
+#[reefer::algebraic(selftest)]
+#[allow(dead_code)]
+mod pga2d {
+    use super::*;
+    use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Not, Rem, Shl, Shr, Sub};
+
+    square!(e0, 0);
+    square!(e1, 1);
+    square!(e2, 1);
+
+    shape!(Scalar, Mv<scalar>);
+    shape!(Vector, Mv<e0, e1, e2>);
+    shape!(Bivector, Mv<e01, e20, e12>);
+    shape!(Pseudo, Mv<e012>);
+    shape!(Even, Mv<scalar, e01, e20, e12>);
+    shape!(Odd, Mv<e0, e1, e2, e012>);
+    shape!(Full, Mv<scalar, e0, e1, e2, e01, e20, e12, e012>);
+    shape!(Middle, Mv<e0, e1, e2, e01, e20, e12>);
+    shape!(Direction, Mv<e01, e20>);
+    shape!(Origin, Mv<e12>);
+
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Add<B> for A {
+        type Output = impl Vector;
+        fn add(self, rhs: B) -> Self::Output {
+            self + rhs
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Scalar as B)]
+    impl Add<B> for A {
+        type Output = impl Even;
+        fn add(self, rhs: B) -> Self::Output {
+            self + rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Sub<B> for A {
+        type Output = impl Vector;
+        fn sub(self, rhs: B) -> Self::Output {
+            self - rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Mul<B> for A {
+        type Output = impl Even;
+        fn mul(self, rhs: B) -> Self::Output {
+            self * rhs
+        }
+    }
+    #[reify(Even as A)]
+    #[reify(Even as B)]
+    impl Mul<B> for A {
+        type Output = impl Even;
+        fn mul(self, rhs: B) -> Self::Output {
+            self * rhs
+        }
+    }
+    #[reify(Even as A)]
+    #[reify(Vector as B)]
+    impl Mul<B> for A {
+        type Output = impl Odd;
+        fn mul(self, rhs: B) -> Self::Output {
+            self * rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl BitXor<B> for A {
+        type Output = impl Bivector;
+        fn bitxor(self, rhs: B) -> Self::Output {
+            self ^ rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Bivector as B)]
+    impl BitXor<B> for A {
+        type Output = impl Pseudo;
+        fn bitxor(self, rhs: B) -> Self::Output {
+            self ^ rhs
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl BitAnd<B> for A {
+        type Output = impl Vector;
+        fn bitand(self, rhs: B) -> Self::Output {
+            self & rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl BitOr<B> for A {
+        type Output = impl Scalar;
+        fn bitor(self, rhs: B) -> Self::Output {
+            self | rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Bivector as B)]
+    impl BitOr<B> for A {
+        type Output = impl Vector;
+        fn bitor(self, rhs: B) -> Self::Output {
+            self | rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Bivector as B)]
+    impl Shl<B> for A {
+        type Output = impl Vector;
+        fn shl(self, rhs: B) -> Self::Output {
+            self << rhs
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Vector as B)]
+    impl Shr<B> for A {
+        type Output = impl Vector;
+        fn shr(self, rhs: B) -> Self::Output {
+            self >> rhs
+        }
+    }
+    #[reify(Even as A)]
+    #[reify(Vector as B)]
+    impl Rem<B> for A {
+        type Output = impl Vector;
+        fn rem(self, rhs: B) -> Self::Output {
+            (self % rhs).simplify()
+        }
+    }
+    #[reify(Vector as A)]
+    impl Neg for A {
+        type Output = impl Vector;
+        fn neg(self) -> Self::Output {
+            -self
+        }
+    }
+    #[reify(Vector as A)]
+    impl Not for A {
+        type Output = impl Bivector;
+        fn not(self) -> Self::Output {
+            !self
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Dot<B> for A {
+        type Output = impl Scalar;
+        fn dot(self, rhs: B) -> Self::Output {
+            self.dot(rhs)
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Part<B> for A {
+        type Output = impl Bivector;
+        fn part(self, rhs: B) -> Self::Output {
+            (self * rhs).grade(2)
+        }
+    }
+    #[reify(Even as A)]
+    impl Involute for A {
+        type Output = impl Even;
+        fn aut(self) -> Self::Output {
+            self.aut()
+        }
+        fn rev(self) -> Self::Output {
+            self.rev()
+        }
+        fn conj(self) -> Self::Output {
+            self.conj()
+        }
+    }
+    #[reify(Full as A)]
+    impl Flip for A {
+        type Output = impl Full;
+        /// the reverse, spelled out
+        fn tilde(self) -> Self::Output {
+            self.flip(2, 3)
+        }
+        fn clifford(self) -> Self::Output {
+            self.flip(1, 2)
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Join<B> for A {
+        type Output = impl Middle;
+        fn join(self, rhs: B) -> Self::Output {
+            self.join(rhs)
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl Meet<B> for A {
+        type Output = impl Middle;
+        fn meet(self, rhs: B) -> Self::Output {
+            self.meet(rhs)
+        }
+    }
+    #[reify(Full as A)]
+    impl Parts for A {
+        type Even = impl Even;
+        type Odd = impl Odd;
+        type Middle = impl Middle;
+        fn even(self) -> Self::Even {
+            self.even()
+        }
+        fn odd(self) -> Self::Odd {
+            self.odd()
+        }
+        fn middle(self) -> Self::Middle {
+            self.grade_range(1..3)
+        }
+    }
+    #[reify(Vector as A)]
+    impl Duality for A {
+        type Output = impl Bivector;
+        fn dual(self) -> Self::Output {
+            self.dual()
+        }
+        fn undual(self) -> Self::Output {
+            self.undual()
+        }
+    }
+    #[reify(Vector as A)]
+    impl Square for A {
+        type Output = impl Scalar;
+        fn square(self) -> Self::Output {
+            self.pow(2).simplify()
+        }
+    }
+    #[reify(Bivector as A)]
+    impl Scale for A {
+        type Output = impl Even;
+        fn scale(self, k: Field) -> Self::Output {
+            self * k + mv!(scalar: 1.0)
+        }
+    }
+    #[reify(Vector as A)]
+    impl Norm for A {
+        type Output = impl Vector;
+        fn norm(&self) -> Field {
+            self.norm().scalar
+        }
+        fn normed(self) -> Self::Output {
+            self.normed()
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Component<B> for A {
+        fn component(self, rhs: B) -> Field {
+            (self ^ rhs)[Basis::e20] + rhs.get(Basis::e0)
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Compare<B> for A {
+        fn equals(self, rhs: B) -> bool {
+            (self ^ rhs) == -(rhs ^ self)
+        }
+        fn differs(self, rhs: B) -> bool {
+            self != rhs
+        }
+        fn below(self, rhs: B) -> bool {
+            self.dot(rhs) < 0.0
+        }
+        fn at_most(self, rhs: B) -> bool {
+            (self | rhs) <= self.dot(self)
+        }
+        fn above(self, rhs: B) -> bool {
+            self.dot(rhs) > rhs.dot(rhs)
+        }
+        fn at_least(self, rhs: B) -> bool {
+            (self ^ rhs)[Basis::e12] >= 0.0
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Projection<B> for A {
+        type Output = impl Vector;
+        fn project(self, rhs: B) -> Self::Output {
+            self.project(rhs).simplify()
+        }
+        fn reject(self, rhs: B) -> Self::Output {
+            self.reject(rhs).simplify()
+        }
+        fn reflect(self, rhs: B) -> Self::Output {
+            self.reflect(rhs).simplify()
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl Antimul<B> for A {
+        type Output = impl Odd;
+        fn antimul(self, rhs: B) -> Self::Output {
+            self.antimul(rhs)
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl Antiwedge<B> for A {
+        type Output = impl Vector;
+        fn antiwedge(self, rhs: B) -> Self::Output {
+            self.antiwedge(rhs)
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl Antidot<B> for A {
+        type Output = impl Pseudo;
+        fn antidot(self, rhs: B) -> Self::Output {
+            self.antidot(rhs)
+        }
+    }
+    #[reify(Odd as A)]
+    #[reify(Bivector as B)]
+    impl Antisandwich<B> for A {
+        type Output = impl Bivector;
+        fn antisandwich(self, rhs: B) -> Self::Output {
+            self.antisandwich(rhs).simplify()
+        }
+    }
+    #[reify(Vector as A)]
+    impl Complement for A {
+        type Output = impl Bivector;
+        type Bulk = impl Direction;
+        type Weight = impl Origin;
+        fn right_complement(self) -> Self::Output {
+            self.right_complement()
+        }
+        fn left_complement(self) -> Self::Output {
+            self.left_complement()
+        }
+        fn bulk_right_complement(self) -> Self::Bulk {
+            self.bulk_right_complement()
+        }
+        fn bulk_left_complement(self) -> Self::Bulk {
+            self.bulk_left_complement()
+        }
+        fn weight_right_complement(self) -> Self::Weight {
+            self.weight_right_complement()
+        }
+        fn weight_left_complement(self) -> Self::Weight {
+            self.weight_left_complement()
+        }
+        fn hodge(self) -> Self::Bulk {
+            self.hodge()
+        }
+    }
+    #[reify(Bivector as A)]
+    impl Weights for A {
+        type Output = impl Bivector;
+        type Bulk = impl Origin;
+        type Weight = impl Direction;
+        fn bulk(self) -> Self::Bulk {
+            self.bulk()
+        }
+        fn weight(self) -> Self::Weight {
+            self.weight()
+        }
+        fn bulk_norm(self) -> Field {
+            self.bulk_norm().scalar
+        }
+        fn weight_norm(self) -> Field {
+            self.weight_norm().scalar
+        }
+        fn unitize(self) -> Self::Output {
+            self.unitize()
+        }
+    }
+    #[reify(Bivector as A)]
+    impl Factors for A {
+        type Output = [impl Vector; 2];
+        fn factors(self) -> Self::Output {
+            self.factorize()
+        }
+    }
+    #[reify(Vector as V)]
+    impl V {
+        pub fn transform(self, m: [[Field; DIM]; DIM]) -> impl Vector {
+            self.outermorphism(m)
+        }
+    }
+    #[reify(Even as M)]
+    impl M {
+        pub fn matrix(self) -> [[Field; DIM]; DIM] {
+            self.to_matrix()
+        }
+    }
+}
+
+#[reefer::algebraic(selftest)]
+#[allow(dead_code)]
+mod sta {
+    use super::*;
+    use std::ops::{BitAnd, BitOr, BitXor, Mul, Not, Rem, Shl};
+
+    square!(e1, 1);
+    square!(e2, -1);
+    square!(e3, -1);
+    square!(e4, -1);
+
+    shape!(Scalar, Mv<scalar>);
+    shape!(Vector, Mv<e1, e2, e3, e4>);
+    shape!(Bivector, Mv<e12, e13, e14, e23, e24, e34>);
+    shape!(Trivector, Mv<e123, e124, e134, e234>);
+    shape!(Pseudo, Mv<e1234>);
+    shape!(
+        Even,
+        Mv<scalar, e12, e13, e14, e23, e24, e34>,
+        Mv<scalar, e12, e13, e14, e23, e24, e34, e1234>,
+        Mv<scalar, e1234>
+    );
+
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Mul<B> for A {
+        type Output = impl Even;
+        fn mul(self, rhs: B) -> Self::Output {
+            self * rhs
+        }
+    }
+    #[reify(Even as A)]
+    #[reify(Even as B)]
+    impl Mul<B> for A {
+        type Output = impl Even;
+        fn mul(self, rhs: B) -> Self::Output {
+            self * rhs
+        }
+    }
+    #[reify(Even as A)]
+    #[reify(Vector as B)]
+    impl Rem<B> for A {
+        type Output = impl Vector;
+        fn rem(self, rhs: B) -> Self::Output {
+            (self % rhs).simplify()
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl BitXor<B> for A {
+        type Output = impl Pseudo;
+        fn bitxor(self, rhs: B) -> Self::Output {
+            self ^ rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Bivector as B)]
+    impl BitXor<B> for A {
+        type Output = impl Trivector;
+        fn bitxor(self, rhs: B) -> Self::Output {
+            self ^ rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Trivector as B)]
+    impl BitOr<B> for A {
+        type Output = impl Bivector;
+        fn bitor(self, rhs: B) -> Self::Output {
+            self | rhs
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Trivector as B)]
+    impl Shl<B> for A {
+        type Output = impl Vector;
+        fn shl(self, rhs: B) -> Self::Output {
+            self << rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Trivector as B)]
+    impl BitAnd<B> for A {
+        type Output = impl Scalar;
+        fn bitand(self, rhs: B) -> Self::Output {
+            self & rhs
+        }
+    }
+    #[reify(Vector as A)]
+    impl Not for A {
+        type Output = impl Trivector;
+        fn not(self) -> Self::Output {
+            !self
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl Commutate<B> for A {
+        type Output = impl Bivector;
+        fn commutate(self, rhs: B) -> Self::Output {
+            self.commutate(rhs)
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl Anticomm<B> for A {
+        type Output = impl Even;
+        fn anticomm(self, rhs: B) -> Self::Output {
+            self.anticomm(rhs)
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Part<B> for A {
+        type Output = impl Bivector;
+        fn part(self, rhs: B) -> Self::Output {
+            (self * rhs).grade(2)
+        }
+    }
+    #[reify(Even as A)]
+    impl Involute for A {
+        type Output = impl Even;
+        fn aut(self) -> Self::Output {
+            self.aut()
+        }
+        fn rev(self) -> Self::Output {
+            self.rev()
+        }
+        fn conj(self) -> Self::Output {
+            self.conj()
+        }
+    }
+    #[reify(Bivector as A)]
+    impl Square for A {
+        type Output = impl Even;
+        fn square(self) -> Self::Output {
+            self.pow(2).simplify()
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Compare<B> for A {
+        fn equals(self, rhs: B) -> bool {
+            (self ^ rhs) == -(rhs ^ self)
+        }
+        fn differs(self, rhs: B) -> bool {
+            self != rhs
+        }
+        fn below(self, rhs: B) -> bool {
+            self.dot(rhs) < 0.0
+        }
+        fn at_most(self, rhs: B) -> bool {
+            (self | rhs) <= self.dot(self)
+        }
+        fn above(self, rhs: B) -> bool {
+            self.dot(rhs) > rhs.dot(rhs)
+        }
+        fn at_least(self, rhs: B) -> bool {
+            (self ^ rhs)[Basis::e12] >= 0.0
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Projection<B> for A {
+        type Output = impl Vector;
+        fn project(self, rhs: B) -> Self::Output {
+            self.project(rhs).simplify()
+        }
+        fn reject(self, rhs: B) -> Self::Output {
+            self.reject(rhs).simplify()
+        }
+        fn reflect(self, rhs: B) -> Self::Output {
+            self.reflect(rhs).simplify()
+        }
+    }
+    #[reify(Bivector as A)]
+    impl Split for A {
+        type Output = (impl Bivector, impl Bivector);
+        fn split(self) -> Self::Output {
+            self.split()
+        }
+    }
+    #[reify(Trivector as A)]
+    impl Factors for A {
+        type Output = [impl Vector; 3];
+        fn factors(self) -> Self::Output {
+            self.factorize()
+        }
+    }
+    #[reify(Bivector as B)]
+    impl B {
+        pub fn transform(self, m: [[Field; DIM]; DIM]) -> impl Bivector {
+            self.outermorphism(m)
+        }
+    }
+    #[reify(Even as M)]
+    impl M {
+        pub fn matrix(self) -> [[Field; DIM]; DIM] {
+            self.to_matrix()
+        }
+    }
+}
+
+#[reefer::algebraic(selftest)]
+#[allow(dead_code)]
+mod cga3d {
+    use super::*;
+    use std::ops::{BitAnd, BitOr, BitXor, Mul, Not, Shl, Shr};
+
+    square!(e1, 1);
+    square!(e2, 1);
+    square!(e3, 1);
+    square!(e4, 1);
+    square!(e5, -1);
+
+    shape!(Scalar, Mv<scalar>);
+    shape!(Vector, Mv<e1, e2, e3, e4, e5>);
+    shape!(Bivector, Mv<e12, e13, e14, e15, e23, e24, e25, e34, e35, e45>);
+    shape!(Trivector, Mv<e123, e124, e125, e134, e135, e145, e234, e235, e245, e345>);
+    shape!(Antivector, Mv<e1234, e1235, e1245, e1345, e2345>);
+    shape!(Pseudo, Mv<e12345>);
+    shape!(Spinor, Mv<scalar, e12, e13, e14, e15, e23, e24, e25, e34, e35, e45>);
+
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl BitXor<B> for A {
+        type Output = impl Bivector;
+        fn bitxor(self, rhs: B) -> Self::Output {
+            self ^ rhs
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl BitXor<B> for A {
+        type Output = impl Antivector;
+        fn bitxor(self, rhs: B) -> Self::Output {
+            self ^ rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Antivector as B)]
+    impl BitXor<B> for A {
+        type Output = impl Pseudo;
+        fn bitxor(self, rhs: B) -> Self::Output {
+            self ^ rhs
+        }
+    }
+    #[reify(Antivector as A)]
+    #[reify(Antivector as B)]
+    impl BitAnd<B> for A {
+        type Output = impl Trivector;
+        fn bitand(self, rhs: B) -> Self::Output {
+            self & rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl BitOr<B> for A {
+        type Output = impl Scalar;
+        fn bitor(self, rhs: B) -> Self::Output {
+            self | rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Antivector as B)]
+    impl Shl<B> for A {
+        type Output = impl Trivector;
+        fn shl(self, rhs: B) -> Self::Output {
+            self << rhs
+        }
+    }
+    #[reify(Antivector as A)]
+    #[reify(Vector as B)]
+    impl Shr<B> for A {
+        type Output = impl Trivector;
+        fn shr(self, rhs: B) -> Self::Output {
+            self >> rhs
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Mul<B> for A {
+        type Output = impl Spinor;
+        fn mul(self, rhs: B) -> Self::Output {
+            self * rhs
+        }
+    }
+    #[reify(Vector as A)]
+    impl Not for A {
+        type Output = impl Antivector;
+        fn not(self) -> Self::Output {
+            !self
+        }
+    }
+    #[reify(Bivector as A)]
+    impl Duality for A {
+        type Output = impl Trivector;
+        fn dual(self) -> Self::Output {
+            self.dual()
+        }
+        fn undual(self) -> Self::Output {
+            self.undual()
+        }
+    }
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    impl Commutate<B> for A {
+        type Output = impl Bivector;
+        fn commutate(self, rhs: B) -> Self::Output {
+            self.commutate(rhs)
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Anticomm<B> for A {
+        type Output = impl Scalar;
+        fn anticomm(self, rhs: B) -> Self::Output {
+            self.anticomm(rhs)
+        }
+    }
+    #[reify(Spinor as A)]
+    impl Involute for A {
+        type Output = impl Spinor;
+        fn aut(self) -> Self::Output {
+            self.aut()
+        }
+        fn rev(self) -> Self::Output {
+            self.rev()
+        }
+        fn conj(self) -> Self::Output {
+            self.conj()
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Bivector as B)]
+    impl Part<B> for A {
+        type Output = impl Trivector;
+        fn part(self, rhs: B) -> Self::Output {
+            (self * rhs).grade(3)
+        }
+    }
+    #[reify(Vector as A)]
+    impl Square for A {
+        type Output = impl Scalar;
+        fn square(self) -> Self::Output {
+            self.pow(2).simplify()
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    impl Compare<B> for A {
+        fn equals(self, rhs: B) -> bool {
+            (self ^ rhs) == -(rhs ^ self)
+        }
+        fn differs(self, rhs: B) -> bool {
+            self != rhs
+        }
+        fn below(self, rhs: B) -> bool {
+            self.dot(rhs) < 0.0
+        }
+        fn at_most(self, rhs: B) -> bool {
+            (self | rhs) <= self.dot(self)
+        }
+        fn above(self, rhs: B) -> bool {
+            self.dot(rhs) > rhs.dot(rhs)
+        }
+        fn at_least(self, rhs: B) -> bool {
+            (self ^ rhs)[Basis::e12] >= 0.0
+        }
+    }
+    #[reify(Vector as A)]
+    #[reify(Bivector as B)]
+    impl Projection<B> for A {
+        type Output = impl Vector;
+        fn project(self, rhs: B) -> Self::Output {
+            self.project(rhs).grade(1)
+        }
+        fn reject(self, rhs: B) -> Self::Output {
+            self.reject(rhs).grade(1)
+        }
+        fn reflect(self, rhs: B) -> Self::Output {
+            self.reflect(rhs).grade(1)
+        }
+    }
+    #[reify(Bivector as A)]
+    impl Complement for A {
+        type Output = impl Trivector;
+        type Bulk = impl Trivector;
+        type Weight = impl Trivector;
+        fn right_complement(self) -> Self::Output {
+            self.right_complement()
+        }
+        fn left_complement(self) -> Self::Output {
+            self.left_complement()
+        }
+        fn bulk_right_complement(self) -> Self::Bulk {
+            self.bulk_right_complement()
+        }
+        fn bulk_left_complement(self) -> Self::Bulk {
+            self.bulk_left_complement()
+        }
+        fn weight_right_complement(self) -> Self::Weight {
+            self.weight_right_complement()
+        }
+        fn weight_left_complement(self) -> Self::Weight {
+            self.weight_left_complement()
+        }
+        fn hodge(self) -> Self::Bulk {
+            self.hodge()
+        }
+    }
+    #[reify(Bivector as A)]
+    impl Split for A {
+        type Output = (impl Bivector, impl Bivector);
+        fn split(self) -> Self::Output {
+            self.split()
+        }
+    }
+    #[reify(Antivector as A)]
+    impl Factors for A {
+        type Output = [impl Vector; 4];
+        fn factors(self) -> Self::Output {
+            self.factorize()
+        }
+    }
+    #[reify(Vector as V)]
+    impl V {
+        pub fn transform(self, m: [[Field; DIM]; DIM]) -> impl Vector {
+            self.outermorphism(m)
+        }
+    }
+    #[reify(Spinor as M)]
+    impl M {
+        pub fn matrix(self) -> [[Field; DIM]; DIM] {
+            self.to_matrix()
+        }
+    }
+}
@@ -0,0 +1,141 @@
+#[reefer::algebraic(ffi)]
+#[allow(dead_code)]
+mod pga2d {
+    use std::ops::{BitAnd, BitXor};
+
+    type Field = f32;
+
+    trait Pow {
+        fn pow(self, n: Self) -> Self;
+    }
+    impl Pow for Field {
+        fn pow(self, n: Self) -> Self {
+            self.powf(n)
+        }
+    }
+
+    square!(e0, 0);
+    square!(e1, 1);
+    square!(e2, 1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Line, Mv<e1, e2, e0>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Point, Mv<e01, e20, e12>);
+
+    #[reify(Line as A)]
+    #[reify(Line as B)]
+    impl BitXor<B> for A {
+        type Output = impl Point;
+        /// meet two lines into a point
+        fn bitxor(self, line: B) -> Self::Output {
+            self ^ line
+        }
+    }
+
+    #[reify(Point as A)]
+    #[reify(Point as B)]
+    impl BitAnd<B> for A {
+        type Output = impl Line;
+        /// join two points into a line
+        fn bitand(self, point: B) -> Self::Output {
+            self & point
+        }
+    }
+
+    pub trait Weight {
+        fn weight(&self) -> Field;
+        fn scaled(self, k: Field) -> Self;
+    }
+    #[reify(Line as L)]
+    impl Weight for L {
+        fn weight(&self) -> Field {
+            self.norm().scalar
+        }
+        fn scaled(self, k: Field) -> Self {
+            self * k
+        }
+    }
+
+    /// a second `scaled` of the same shapes, told apart by the trait in the symbol
+    pub trait Grow {
+        fn scaled(self, k: Field) -> Self;
+    }
+    #[reify(Line as L)]
+    impl Grow for L {
+        fn scaled(self, k: Field) -> Self {
+            self * (k + 1.0)
+        }
+    }
+}
+
+use pga2d::{Mv_e1_e2_e0 as Line, Mv_e01_e20_e12 as Point};
+
+unsafe extern "C" {
+    fn pga2d_bitxor_mv_e1_e2_e0_mv_e1_e2_e0(a: Line, b: Line) -> Point;
+}
+
+#[test]
+fn test_extern_fns() {
+    let a = Line {
+        e1: 1.0,
+        e2: 0.0,
+        e0: -1.0,
+    };
+    let b = Line {
+        e1: 0.0,
+        e2: 1.0,
+        e0: -2.0,
+    };
+    let meet = unsafe { pga2d_bitxor_mv_e1_e2_e0_mv_e1_e2_e0(a, b) };
+    assert_eq!(meet, a ^ b);
+    assert_eq!(
+        pga2d::pga2d_bitand_mv_e01_e20_e12_mv_e01_e20_e12(meet, meet),
+        meet & meet
+    );
+    assert_eq!(pga2d::pga2d_weight_mv_e1_e2_e0(a), 1.0);
+    assert_eq!(
+        pga2d::pga2d_weight_scaled_mv_e1_e2_e0_field(b, 2.0).e0,
+        -4.0
+    );
+    assert_eq!(pga2d::pga2d_grow_scaled_mv_e1_e2_e0_field(b, 2.0).e0, -6.0);
+}
+
+#[test]
+fn test_header() {
+    let header = pga2d::C_HEADER;
+    assert!(header.contains("typedef struct pga2d_Mv_e1_e2_e0 {\n    float e1;\n    float e2;\n    float e0;\n} pga2d_Mv_e1_e2_e0;"));
+    assert!(header.contains(
+        "pga2d_Mv_e01_e20_e12 pga2d_bitxor_mv_e1_e2_e0_mv_e1_e2_e0(pga2d_Mv_e1_e2_e0 self_, pga2d_Mv_e1_e2_e0 line);"
+    ));
+    assert!(header.contains("float pga2d_weight_mv_e1_e2_e0(pga2d_Mv_e1_e2_e0 self_);"));
+    // only written when asked for
+    let Some(dir) = option_env!("REEFER_DUMP_DIR") else {
+        return;
+    };
+    let dir = match option_env!("CARGO_TARGET_DIR") {
+        Some(target) => std::path::Path::new(target).join(dir),
+        None => std::path::PathBuf::from(dir),
+    };
+    assert_eq!(
+        std::fs::read_to_string(dir.join("pga2d.h")).unwrap(),
+        header
+    );
+}
+
+#[test]
+fn test_header_compiles() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    // only checked where a C compiler is around
+    let Ok(mut cc) = Command::new("cc")
+        .args(["-fsyntax-only", "-Wall", "-Werror", "-x", "c", "-"])
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        return;
+    };
+    let src = format!("{}\nint main(void) {{ return 0; }}\n", pga2d::C_HEADER);
+    cc.stdin.take().unwrap().write_all(src.as_bytes()).unwrap();
+    assert!(cc.wait().unwrap().success());
+}
@@ -1,7 +1,7 @@
 #[reefer::algebraic]
 /// Create a timespace Algebra with 1,3 metric.
 mod sta {
-    use std::ops::{Add, BitAnd, BitOr, BitXor, Rem};
+    use std::ops::{Add, Rem};
 
     type Field = f32;
 
@@ -66,6 +66,7 @@ use libm::{atanhf, fabsf};
 use reefer::mv;
 
 #[test]
+#[allow(clippy::excessive_precision)] // speed of light is given in km/s, keep the exact value
 /// from https://enkimute.github.io/ganja.js/examples/coffeeshop.html#timespace_lorentz
 fn test_lorentz() {
     use sta::*;
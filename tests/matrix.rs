@@ -0,0 +1,125 @@
+type Field = f64;
+
+pub trait Pow {
+    fn pow(self, n: Self) -> Self;
+}
+impl Pow for Field {
+    fn pow(self, n: Self) -> Self {
+        self.powf(n)
+    }
+}
+
+pub trait Transform<Rhs> {
+    type Output;
+    fn transform(self, rhs: Rhs) -> Self::Output;
+}
+
+#[reefer::algebraic]
+#[allow(dead_code)]
+mod pga3d {
+    use super::*;
+
+    square!(e0, 0);
+    square!(e1, 1);
+    square!(e2, 1);
+    square!(e3, 1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Plane, Mv<e1, e2, e3, e0>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Motor, Mv<scalar, e23, e31, e12, e01, e02, e03, e0123>);
+
+    #[reify(Motor as M)]
+    impl M {
+        /// acts on planes, the grade 1 elements
+        pub fn matrix(self) -> [[Field; 4]; 4] {
+            self.to_matrix()
+        }
+        pub fn from_matrix(m: [[Field; DIM]; DIM]) -> impl Motor {
+            from_matrix(m)
+        }
+    }
+
+    #[reify(Motor as M)]
+    #[reify(Plane as P)]
+    impl Transform<P> for M {
+        type Output = impl Plane;
+        fn transform(self, plane: P) -> Self::Output {
+            (self % plane).simplify()
+        }
+    }
+}
+
+use pga3d::{Mv_e1_e2_e3_e0 as Plane, Mv_scalar_e23_e31_e12_e01_e02_e03_e0123 as Motor};
+
+/// a rotation about z by `angle`, then a translation of `(x, y, z)` through `e0i = -t/2`
+fn motor(angle: Field, x: Field, y: Field, z: Field) -> Motor {
+    let (c, s) = ((angle / 2.0).cos(), (angle / 2.0).sin());
+    let (x, y, z) = (x / 2.0, y / 2.0, z / 2.0);
+    // (1 - x e01 - y e02 - z e03) * (c - s e12)
+    Motor {
+        scalar: c,
+        e23: 0.0,
+        e31: 0.0,
+        e12: -s,
+        e01: -x * c - y * s,
+        e02: -y * c + x * s,
+        e03: -z * c,
+        e0123: z * s,
+    }
+}
+
+fn assert_near(a: &[Field], b: &[Field]) {
+    let close = a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-12);
+    assert!(close, "{a:?} != {b:?}");
+}
+
+fn motor_values(m: Motor) -> [Field; 8] {
+    [m.scalar, m.e23, m.e31, m.e12, m.e01, m.e02, m.e03, m.e0123]
+}
+
+#[test]
+fn test_matrix_is_the_sandwich() {
+    let m = motor(0.7, 1.0, -2.0, 0.5);
+    let matrix = m.matrix();
+    // columns are the images of e0, e1, e2, e3
+    for (j, plane) in [
+        [0.0, 0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let [e1, e2, e3, e0] = plane;
+        let image = m.transform(Plane { e1, e2, e3, e0 });
+        let column = [matrix[0][j], matrix[1][j], matrix[2][j], matrix[3][j]];
+        assert_near(&column, &[image.e0, image.e1, image.e2, image.e3]);
+    }
+}
+
+#[test]
+fn test_rotation_matrix() {
+    let matrix = motor(std::f64::consts::FRAC_PI_2, 0.0, 0.0, 0.0).matrix();
+    // e1 turns into e2 and e0 is fixed
+    assert_near(
+        &[matrix[0][1], matrix[1][1], matrix[2][1], matrix[3][1]],
+        &[0.0, 0.0, 1.0, 0.0],
+    );
+    assert_near(&[matrix[0][0]], &[1.0]);
+}
+
+#[test]
+fn test_from_matrix_roundtrip() {
+    for m in [
+        motor(0.7, 1.0, -2.0, 0.5),
+        motor(-1.2, 0.0, 3.0, -1.0),
+        motor(0.0, 2.0, 0.0, 0.0),
+    ] {
+        assert_near(
+            &motor_values(Motor::from_matrix(m.matrix())),
+            &motor_values(m),
+        );
+    }
+}
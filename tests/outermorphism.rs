@@ -0,0 +1,112 @@
+pub trait Transform {
+    type Output;
+    fn transform(self, m: [[Field; 3]; 3]) -> Self::Output;
+}
+
+type Field = f64;
+
+pub trait Pow {
+    fn pow(self, n: Self) -> Self;
+}
+impl Pow for Field {
+    fn pow(self, n: Self) -> Self {
+        self.powf(n)
+    }
+}
+
+#[reefer::algebraic]
+#[allow(dead_code)]
+mod vga3d {
+    use super::*;
+
+    square!(e1, 1);
+    square!(e2, 1);
+    square!(e3, 1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Vector, Mv<e1, e2, e3>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Bivector, Mv<e23, e31, e12>);
+
+    #[reify(Vector as V)]
+    impl Transform for V {
+        type Output = impl Vector;
+        fn transform(self, m: [[Field; 3]; 3]) -> Self::Output {
+            self.outermorphism(m)
+        }
+    }
+
+    #[reify(Bivector as B)]
+    impl Transform for B {
+        type Output = impl Bivector;
+        fn transform(self, m: [[Field; DIM]; DIM]) -> Self::Output {
+            self.outermorphism(m)
+        }
+    }
+
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    /// the wedge of the images, to check against the image of the wedge
+    pub fn wedge_images(a: A, b: B, m: [[Field; 3]; 3]) -> impl Bivector {
+        a.outermorphism(m) ^ b.outermorphism(m)
+    }
+
+    #[reify(Vector as V)]
+    /// the scale of volumes, the image of the pseudoscalar
+    pub fn det(v: V, m: [[Field; 3]; 3]) -> Field {
+        mv!(e123: 1.0).outermorphism(m).e123
+    }
+}
+
+use vga3d::{Mv_e1_e2_e3 as Vector, Mv_e23_e31_e12 as Bivector};
+
+const SCALE: [[Field; 3]; 3] = [[2.0, 0.0, 0.0], [0.0, 3.0, 0.0], [0.0, 0.0, 4.0]];
+/// x += 2y
+const SHEAR: [[Field; 3]; 3] = [[1.0, 2.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+fn vector(e1: Field, e2: Field, e3: Field) -> Vector {
+    Vector { e1, e2, e3 }
+}
+
+#[test]
+fn test_scale() {
+    assert_eq!(
+        vector(1.0, 1.0, 1.0).transform(SCALE),
+        vector(2.0, 3.0, 4.0)
+    );
+    let b = Bivector {
+        e23: 1.0,
+        e31: 1.0,
+        e12: 1.0,
+    };
+    let scaled = b.transform(SCALE);
+    assert_eq!((scaled.e23, scaled.e31, scaled.e12), (12.0, 8.0, 6.0));
+    assert_eq!(vga3d::det(vector(0.0, 0.0, 0.0), SCALE), 24.0);
+}
+
+#[test]
+fn test_shear() {
+    assert_eq!(
+        vector(0.0, 1.0, 0.0).transform(SHEAR),
+        vector(2.0, 1.0, 0.0)
+    );
+    // shears keep volumes
+    assert_eq!(vga3d::det(vector(0.0, 0.0, 0.0), SHEAR), 1.0);
+}
+
+#[test]
+fn test_wedge_of_images() {
+    let m = [[1.0, 2.0, 0.5], [-1.0, 0.0, 3.0], [2.0, 1.0, 1.0]];
+    let (a, b) = (vector(1.0, 2.0, 3.0), vector(-2.0, 0.5, 1.0));
+    // `a ^ b` as a bivector
+    let wedge = Bivector {
+        e23: a.e2 * b.e3 - a.e3 * b.e2,
+        e31: a.e3 * b.e1 - a.e1 * b.e3,
+        e12: a.e1 * b.e2 - a.e2 * b.e1,
+    };
+    let lhs = vga3d::wedge_images(a, b, m);
+    let rhs = wedge.transform(m);
+    for (l, r) in [(lhs.e23, rhs.e23), (lhs.e31, rhs.e31), (lhs.e12, rhs.e12)] {
+        assert!((l - r).abs() < 1e-12, "{lhs:?} != {rhs:?}");
+    }
+}
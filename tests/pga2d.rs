@@ -1,4 +1,5 @@
 #[reefer::algebraic]
+#[allow(dead_code)]
 mod pga2d {
     use std::ops::{BitAnd, BitOr, BitXor};
 
@@ -20,10 +21,19 @@ mod pga2d {
     shape!(Scalar, Mv<scalar>);
     // shape!(Line, Mv<e1, e2, e0>);
     shape!(Ideal, Mv<Powerset<e01, e20>>);
+    #[derive(Clone, Debug)]
     shape!(Point, Mv<e12>, Mv<e01, e12>, Mv<e02, e12>, Mv<e01, e20, e12>);
+    #[derive(Clone, Debug)]
     shape!(Line, Mv<Powerset<e1, e2, e0>>);
     // shape!(Point, Mv<Powerset<e20, e01, e12>>);
 
+    /// the module's own `Display` replaces the generated one
+    impl std::fmt::Display for Mv_e12 {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "origin * {}", self.e12)
+        }
+    }
+
     #[reify(Line as A)]
     #[reify(Line as B)]
     impl BitXor<B> for A {
@@ -64,7 +74,7 @@ mod pga2d {
         type Output = impl Point;
         /// project point onto line
         fn project(self, line: L) -> Self::Output {
-            ((line | self) * line).simplify()
+            self.project(line).simplify()
         }
     }
 
@@ -74,7 +84,100 @@ mod pga2d {
         type Output = impl Line;
         /// project line onto point
         fn project(self, point: P) -> Self::Output {
-            ((self | point) * point).simplify()
+            self.project(point).simplify()
+        }
+    }
+
+    pub trait Reflection<Rhs> {
+        type Output;
+        fn reflect(self, other: Rhs) -> Self::Output;
+    }
+    #[reify(Point as P)]
+    #[reify(Line as L)]
+    impl Reflection<L> for P {
+        type Output = impl Point;
+        /// reflect point across line, inlining the free function below
+        fn reflect(self, line: L) -> Self::Output {
+            (project_onto(self, line) * mv!(scalar: -2.0) - self * (line | line)).simplify()
+        }
+    }
+
+    #[reify(Point as P)]
+    #[reify(Line as L)]
+    /// project point onto line as a free function
+    #[inline]
+    pub fn project_onto(point: P, line: L) -> impl Point {
+        ((line | point) * line).simplify()
+    }
+
+    pub trait Mirror<Rhs> {
+        type Output;
+        fn mirrored(self, other: Rhs) -> Self::Output;
+    }
+    #[reify(Point as P)]
+    #[reify(Line as L)]
+    impl Mirror<L> for P {
+        type Output = impl Point;
+        /// `Reflection` above shadows the built-in `reflect`
+        fn mirrored(self, line: L) -> Self::Output {
+            self.reflect(line).simplify()
+        }
+    }
+
+    #[reify(Line as L)]
+    impl L {
+        /// same line with the opposite orientation
+        pub fn flipped(self) -> impl Line {
+            -self
+        }
+    }
+
+    const HALF: Field = 0.5;
+
+    pub trait Lerp<Rhs> {
+        type Output;
+        fn lerp(self, other: Rhs, t: Field) -> Self::Output;
+        fn midpoint(self, other: Rhs) -> Self::Output;
+    }
+    #[reify(Point as A)]
+    #[reify(Point as B)]
+    impl Lerp<B> for A {
+        type Output = impl Point;
+        /// linear interpolation, `Field` args and literals act as scalars
+        fn lerp(self, other: B, t: Field) -> Self::Output {
+            self * (1.0 - t) + other * t
+        }
+        fn midpoint(self, other: B) -> Self::Output {
+            HALF * (self + other)
+        }
+    }
+
+    pub trait Toward<Rhs> {
+        type Output;
+        fn toward(self, dir: Rhs) -> Self::Output;
+    }
+    #[reify(Point as P)]
+    #[reify(Ideal as I)]
+    impl Toward<I> for P {
+        type Output = impl Point;
+        /// the ideal `dir` has no `e12` and is passed to `lerp` as a `Point` with a zero weight
+        fn toward(self, dir: I) -> Self::Output {
+            self.lerp(dir, 0.5)
+        }
+    }
+
+    pub trait Incidence<Rhs> {
+        fn incident(self, other: Rhs) -> bool;
+        fn left_of(self, other: Rhs) -> bool;
+    }
+    #[reify(Point as P)]
+    #[reify(Line as L)]
+    impl Incidence<L> for P {
+        fn incident(self, line: L) -> bool {
+            (self & line) == 0.0
+        }
+        fn left_of(self, line: L) -> bool {
+            (self & line) > 0.0
         }
     }
 
@@ -95,6 +198,7 @@ mod pga2d {
         fn normalized(self) -> Self::Output;
         fn norm(self) -> Field;
         fn ideal_norm(self) -> Field;
+        fn norm_squared(self) -> Field;
     }
     #[reify(Line as L)]
     impl Normalize for L {
@@ -106,7 +210,24 @@ mod pga2d {
             self.norm().scalar
         }
         fn ideal_norm(self) -> Field {
-            self.dual().norm().scalar
+            self.weight_norm().scalar
+        }
+        fn norm_squared(self) -> Field {
+            self.dot(self)
+        }
+    }
+
+    pub trait Coords {
+        fn x(self) -> Field;
+        fn y(self) -> Field;
+    }
+    #[reify(Point as P)]
+    impl Coords for P {
+        fn x(self) -> Field {
+            self[Basis::e02]
+        }
+        fn y(self) -> Field {
+            -self.get(Basis::e01)
         }
     }
 
@@ -127,3 +248,156 @@ mod pga2d {
     //     }
     // }
 }
+
+use reefer::mv;
+
+#[test]
+fn test_reified_fn() {
+    use pga2d::*;
+    // the line x = 0 and the point (2, 3)
+    let line = mv![e1: 1.0, e2: 0.0, e0: 0.0];
+    let point = mv![e01: -3.0, e20: -2.0, e12: 1.0];
+    let projected = project_onto(point, line.clone());
+    // lands on (0, 3), up to the homogeneous weight
+    assert_eq!(projected.e20 / projected.e12, 0.0);
+    assert_eq!(projected.e01 / projected.e12, -3.0);
+    assert_eq!(line.flipped().e1, -1.0);
+}
+
+#[test]
+fn test_inlined_call() {
+    use pga2d::*;
+    // reflect (2, 3) across the line x = 0
+    let line = mv![e1: 1.0, e2: 0.0, e0: 0.0];
+    let point = mv![e01: -3.0, e20: -2.0, e12: 1.0];
+    let reflected = point.reflect(line);
+    assert_eq!(reflected.e20 / reflected.e12, 2.0);
+    assert_eq!(reflected.e01 / reflected.e12, -3.0);
+}
+
+#[test]
+fn test_builtin_projection() {
+    use pga2d::*;
+    // (2, 3) and the line x = 0
+    let point = mv![e01: -3.0, e20: -2.0, e12: 1.0];
+    let line = mv![e1: 1.0, e2: 0.0, e0: 0.0];
+    let projected = point.clone().project(line.clone());
+    assert_eq!(
+        (projected.e20, projected.e01, projected.e12),
+        (0.0, -3.0, 1.0)
+    );
+    let mirrored = point.clone().mirrored(line.clone());
+    assert_eq!(mirrored.e20 / mirrored.e12, 2.0);
+    assert_eq!(mirrored.e01 / mirrored.e12, -3.0);
+    // the same weight as the reified reflect, not the built-in one
+    let reflected = point.reflect(line);
+    assert_eq!(
+        (mirrored.e20, mirrored.e01, mirrored.e12),
+        (reflected.e20, reflected.e01, reflected.e12)
+    );
+}
+
+#[test]
+fn test_scalar_mixing() {
+    use pga2d::*;
+    // (2, 3) and (4, -1)
+    let a = mv![e01: -3.0, e20: -2.0, e12: 1.0];
+    let b = mv![e01: 1.0, e20: -4.0, e12: 1.0];
+    let p = a.clone().lerp(b.clone(), 0.25);
+    assert_eq!((p.e20, p.e01, p.e12), (-2.5, -2.0, 1.0));
+    let m = a.midpoint(b);
+    assert_eq!((m.e20, m.e01, m.e12), (-3.0, -1.0, 1.0));
+    let line = mv![e1: 3.0, e2: 4.0, e0: 5.0];
+    assert_eq!(line.clone().norm_squared(), 25.0);
+    assert_eq!(line.clone().norm(), 5.0);
+    assert_eq!(line.ideal_norm(), 5.0);
+}
+
+#[test]
+fn test_shape_equality() {
+    use pga2d::*;
+    // e20 and e02 are the same blade up to sign, missing blades are zero
+    let a = mv![e02: 2.0, e12: 1.0];
+    let b = mv![e01: 0.0, e20: -2.0, e12: 1.0];
+    assert!(a == b);
+    assert!(b != mv![e12: 1.0]);
+    assert!(mv![e01: 0.0, e12: 1.0] == mv![e12: 1.0]);
+    let c = mv![e01: 1e-4, e20: -2.0, e12: 1.0];
+    assert!(a != c);
+    assert!(a.approx_eq(&c, 1e-3));
+    assert!(!a.approx_eq(&c, 1e-5));
+}
+
+#[test]
+fn test_reified_comparison() {
+    use pga2d::*;
+    // the line x = 0 and the points (0, 3), (2, 3), (-2, 3)
+    let line = mv![e1: 1.0, e2: 0.0, e0: 0.0];
+    let on = mv![e01: -3.0, e20: 0.0, e12: 1.0];
+    let right = mv![e01: -3.0, e20: -2.0, e12: 1.0];
+    let left = mv![e01: -3.0, e20: 2.0, e12: 1.0];
+    assert!(on.clone().incident(line.clone()));
+    assert!(!right.clone().incident(line.clone()));
+    assert_ne!(right.left_of(line.clone()), left.left_of(line));
+}
+
+#[test]
+fn test_metric_consts() {
+    use pga2d::*;
+    assert_eq!(DIM, 3);
+    assert_eq!(SQUARES, [0, 1, 1]);
+    assert_eq!(BASIS.len(), 1 << DIM);
+    assert_eq!(BASIS[..4], ["scalar", "e0", "e1", "e2"]);
+    let index = |name| BASIS.iter().position(|b| *b == name).unwrap();
+    assert_eq!(CAYLEY[index("e2")][index("e1")], (-1, index("e12")));
+    assert_eq!(CAYLEY[index("e0")][index("e0")], (0, 0));
+    // the scalar is the identity
+    for (i, row) in CAYLEY.iter().enumerate() {
+        assert_eq!(row[0], (1, i));
+        assert_eq!(CAYLEY[0][i], (1, i));
+    }
+}
+
+#[test]
+fn test_display() {
+    use pga2d::{Mv_e01_e20_e12, Mv_scalar};
+    let point = Mv_e01_e20_e12 {
+        e01: 1.5,
+        e20: 0.0,
+        e12: -0.5,
+    };
+    assert_eq!(point.to_string(), "1.5e01 - 0.5e12");
+    assert_eq!(format!("{point:#}"), "1.5e01 + 0e20 - 0.5e12");
+    assert_eq!(format!("{point:.2}"), "1.50e01 - 0.50e12");
+    let point = Mv_e01_e20_e12 {
+        e01: -2.0,
+        e20: 2.0,
+        e12: 0.0,
+    };
+    assert_eq!(point.to_string(), "-2e01 + 2e20");
+    assert_eq!(Mv_scalar { scalar: 0.0 }.to_string(), "0");
+    assert_eq!(Mv_scalar { scalar: 3.0 }.to_string(), "3");
+    assert_eq!(pga2d::Mv_e12 { e12: 2.0 }.to_string(), "origin * 2");
+}
+
+#[test]
+fn test_basis_index() {
+    use pga2d::*;
+    // (2, 3), the coords assume e12 is 1
+    let a = mv![e01: -3.0, e20: -2.0, e12: 1.0];
+    assert_eq!(a.get(Basis::e02), 2.0);
+    assert_eq!(a.get(Basis::e01), -3.0);
+    assert_eq!(a.get(Basis::e0), 0.0);
+    assert_eq!((a.clone().x(), a.y()), (2.0, 3.0));
+    assert_eq!(BASIS[Basis::e02 as usize], "e02");
+    assert_eq!(Basis::e12.name(), "e12");
+    // shapes without the blade read it as zero
+    assert_eq!(mv![e01: 1.0, e12: 2.0].x(), 0.0);
+}
+
+#[test]
+fn test_inlined_subset() {
+    use pga2d::*;
+    let moved = mv![e12: 1.0].toward(mv![e01: 2.0]);
+    assert_eq!((moved.e01, moved.e12), (1.0, 0.5));
+}
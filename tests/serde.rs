@@ -0,0 +1,91 @@
+#[reefer::algebraic(serde)]
+#[allow(dead_code)]
+mod pga2d {
+    type Field = f32;
+
+    square!(e0, 0);
+    square!(e1, 1);
+    square!(e2, 1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Line, Mv<e1, e2, e0>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Point, Mv<e01, e20, e12>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Motor, Mv<scalar, e01, e20, e12>);
+}
+
+use pga2d::{Mv_e1_e2_e0 as Line, Mv_e01_e20_e12 as Point, Mv_scalar_e01_e20_e12 as Motor};
+
+#[test]
+fn test_roundtrip() {
+    let point = Point {
+        e01: 1.0,
+        e20: -2.0,
+        e12: 0.5,
+    };
+    let json = serde_json::to_string(&point).unwrap();
+    assert_eq!(json, r#"{"e01":1.0,"e20":-2.0,"e12":0.5}"#);
+    assert_eq!(serde_json::from_str::<Point>(&json).unwrap(), point);
+}
+
+#[test]
+fn test_orientation() {
+    let point: Point = serde_json::from_str(r#"{"e10": 1.0, "e02": 2.0, "e21": 3.0}"#).unwrap();
+    assert_eq!(
+        point,
+        Point {
+            e01: -1.0,
+            e20: -2.0,
+            e12: -3.0
+        }
+    );
+    assert_eq!(pga2d::parse_blade("e20"), Some((5, -1)));
+    assert_eq!(pga2d::BASIS[5], "e02");
+    assert_eq!(pga2d::parse_blade("e201"), Some((7, 1)));
+    assert_eq!(pga2d::parse_blade("e102"), Some((7, -1)));
+    assert_eq!(pga2d::parse_blade("scalar"), Some((0, 1)));
+    // repeated and unknown axes aren't blades of the basis
+    assert_eq!(pga2d::parse_blade("e11"), None);
+    assert_eq!(pga2d::parse_blade("e3"), None);
+    assert_eq!(pga2d::parse_blade("12"), None);
+}
+
+#[test]
+fn test_cross_shape() {
+    let point = Point {
+        e01: 1.0,
+        e20: 2.0,
+        e12: 1.0,
+    };
+    let motor: Motor = serde_json::from_str(&serde_json::to_string(&point).unwrap()).unwrap();
+    assert_eq!(
+        motor,
+        Motor {
+            scalar: 0.0,
+            e01: 1.0,
+            e20: 2.0,
+            e12: 1.0
+        }
+    );
+    let point: Point = serde_json::from_str(r#"{"scalar": 0.0, "e12": 1.0}"#).unwrap();
+    assert_eq!(
+        point,
+        Point {
+            e01: 0.0,
+            e20: 0.0,
+            e12: 1.0
+        }
+    );
+}
+
+#[test]
+fn test_errors() {
+    let err = |json| serde_json::from_str::<Line>(json).unwrap_err().to_string();
+    assert!(
+        err(r#"{"e3": 1.0}"#).starts_with("unknown field `e3`, expected one of `e1`, `e2`, `e0`")
+    );
+    assert!(err(r#"{"e12": 1.0}"#).starts_with("`e12` is 1 but Mv_e1_e2_e0 has no such blade"));
+    assert!(err(r#"{"e1": 1.0, "e1": 2.0}"#).starts_with("duplicate blade `e1`"));
+    assert!(err(r#"[1.0]"#).contains("a map of the blades of Mv_e1_e2_e0"));
+}
@@ -0,0 +1,170 @@
+#[reefer::algebraic(wgsl, glsl)]
+#[allow(dead_code)]
+mod pga2d {
+    use std::ops::{BitAnd, BitXor, Mul};
+
+    type Field = f32;
+
+    trait Pow {
+        fn pow(self, n: Self) -> Self;
+    }
+    impl Pow for Field {
+        fn pow(self, n: Self) -> Self {
+            self.powf(n)
+        }
+    }
+
+    const HALF: Field = 0.5;
+
+    square!(e0, 0);
+    square!(e1, 1);
+    square!(e2, 1);
+
+    shape!(Scalar, Mv<scalar>);
+    shape!(Line, Mv<e1, e2, e0>);
+    shape!(Point, Mv<e01, e20, e12>);
+    shape!(Motor, Mv<scalar, e01, e20, e12>);
+    shape!(Flat, Mv<e1, e2, e0, e01, e20, e12>);
+
+    #[reify(Line as A)]
+    #[reify(Line as B)]
+    impl BitXor<B> for A {
+        type Output = impl Point;
+        /// meet two lines into a point
+        fn bitxor(self, line: B) -> Self::Output {
+            self ^ line
+        }
+    }
+
+    #[reify(Point as A)]
+    #[reify(Point as B)]
+    impl BitAnd<B> for A {
+        type Output = impl Line;
+        /// join two points into a line
+        fn bitand(self, point: B) -> Self::Output {
+            self & point
+        }
+    }
+
+    #[reify(Line as A)]
+    #[reify(Line as B)]
+    impl Mul<B> for A {
+        type Output = impl Motor;
+        fn mul(self, line: B) -> Self::Output {
+            self * line
+        }
+    }
+
+    pub trait Sandwich<Rhs> {
+        type Output;
+        fn sandwich(self, rhs: Rhs) -> Self::Output;
+    }
+    #[reify(Motor as M)]
+    #[reify(Point as P)]
+    impl Sandwich<P> for M {
+        type Output = impl Point;
+        fn sandwich(self, point: P) -> Self::Output {
+            (self % point).simplify()
+        }
+    }
+
+    pub trait Meet<Rhs> {
+        type Output;
+        fn meet(self, rhs: Rhs) -> Self::Output;
+    }
+    #[reify(Point as A)]
+    #[reify(Point as B)]
+    impl Meet<B> for A {
+        type Output = impl Flat;
+        /// factorizes at runtime, so there's no shader function for it
+        fn meet(self, point: B) -> Self::Output {
+            self.meet(point)
+        }
+    }
+
+    pub trait Normalize {
+        type Output;
+        fn normalized(self) -> Self::Output;
+        fn norm(self) -> Field;
+        fn scaled(self, k: Field) -> Self::Output;
+    }
+    #[reify(Line as L)]
+    impl Normalize for L {
+        type Output = impl Line;
+        fn normalized(self) -> Self::Output {
+            self.normed()
+        }
+        fn norm(self) -> Field {
+            self.norm().scalar
+        }
+        fn scaled(self, k: Field) -> Self::Output {
+            self * k * HALF
+        }
+    }
+}
+
+fn validate(module: &naga::Module) {
+    let flags = naga::valid::ValidationFlags::all();
+    let caps = naga::valid::Capabilities::empty();
+    if let Err(e) = naga::valid::Validator::new(flags, caps).validate(module) {
+        panic!("{e:?}")
+    }
+}
+
+#[test]
+fn test_wgsl() {
+    let module = naga::front::wgsl::parse_str(pga2d::WGSL)
+        .unwrap_or_else(|e| panic!("{}", e.emit_to_string(pga2d::WGSL)));
+    validate(&module);
+    let fns = module
+        .functions
+        .iter()
+        .map(|(_, f)| f.name.clone().unwrap())
+        .collect::<Vec<_>>();
+    assert!(fns.contains(&"bitxor_Mv_e1_e2_e0_Mv_e1_e2_e0".to_string()));
+    assert!(fns.contains(&"norm_Mv_e1_e2_e0".to_string()));
+    assert!(fns.contains(&"scaled_Mv_e1_e2_e0_Field".to_string()));
+    assert_eq!(fns.len(), 7);
+    assert!(!fns.iter().any(|f| f.starts_with("meet")));
+    assert!(!pga2d::WGSL.contains("meet0_"));
+    // odd powers of a possibly negative field never reach `pow`
+    assert!(!pga2d::WGSL.contains("pow("));
+}
+
+#[test]
+fn test_glsl() {
+    let src = format!(
+        "#version 450\n{}\nlayout(local_size_x = 1) in;\nvoid main() {{}}\n",
+        pga2d::GLSL
+    );
+    let options = naga::front::glsl::Options::from(naga::ShaderStage::Compute);
+    let module = naga::front::glsl::Frontend::default()
+        .parse(&options, &src)
+        .unwrap_or_else(|e| panic!("{}", e.emit_to_string(&src)));
+    validate(&module);
+    assert_eq!(
+        module
+            .types
+            .iter()
+            .filter(|(_, ty)| ty.name.is_some())
+            .count(),
+        5
+    );
+    assert!(!pga2d::GLSL.contains("meet0_"));
+}
+
+#[test]
+fn test_dump_dir() {
+    // only written when asked for
+    let Some(dir) = option_env!("REEFER_DUMP_DIR") else {
+        return;
+    };
+    let dir = match option_env!("CARGO_TARGET_DIR") {
+        Some(target) => std::path::Path::new(target).join(dir),
+        None => std::path::PathBuf::from(dir),
+    };
+    let wgsl = std::fs::read_to_string(dir.join("pga2d.wgsl")).unwrap();
+    assert_eq!(wgsl, pga2d::WGSL);
+    let glsl = std::fs::read_to_string(dir.join("pga2d.glsl")).unwrap();
+    assert_eq!(glsl, pga2d::GLSL);
+}
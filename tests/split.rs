@@ -0,0 +1,219 @@
+type Field = f64;
+
+pub trait Pow {
+    fn pow(self, n: Self) -> Self;
+}
+impl Pow for Field {
+    fn pow(self, n: Self) -> Self {
+        self.powf(n)
+    }
+}
+
+pub trait Split {
+    type Output;
+    fn split(self) -> Self::Output;
+}
+pub trait Pinch {
+    /// `(b ^ b).e0123`, zero for simple bivectors
+    fn pinch(self) -> Field;
+}
+
+#[reefer::algebraic]
+#[allow(dead_code)]
+mod pga3d {
+    use super::*;
+
+    square!(e0, 0);
+    square!(e1, 1);
+    square!(e2, 1);
+    square!(e3, 1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Line, Mv<e23, e31, e12, e01, e02, e03>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(IdealLine, Mv<e01, e02, e03>);
+
+    #[reify(Line as L)]
+    impl Split for L {
+        type Output = (impl Line, impl IdealLine);
+        /// the axis and the ideal line of a screw, without square roots
+        fn split(self) -> Self::Output {
+            self.split()
+        }
+    }
+
+    #[reify(Line as L)]
+    impl Pinch for L {
+        fn pinch(self) -> Field {
+            (self ^ self)[Basis::e0123]
+        }
+    }
+}
+
+#[reefer::algebraic]
+#[allow(dead_code)]
+mod sta {
+    use super::*;
+
+    square!(e0, 1);
+    square!(e1, -1);
+    square!(e2, -1);
+    square!(e3, -1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Bivector, Mv<e01, e02, e03, e23, e31, e12>);
+
+    #[reify(Bivector as B)]
+    impl Split for B {
+        type Output = (impl Bivector, impl Bivector);
+        fn split(self) -> Self::Output {
+            self.split()
+        }
+    }
+
+    #[reify(Bivector as B)]
+    impl Pinch for B {
+        fn pinch(self) -> Field {
+            (self ^ self)[Basis::e0123]
+        }
+    }
+}
+
+#[reefer::algebraic]
+#[allow(dead_code)]
+mod vga4d {
+    use super::*;
+
+    square!(e1, 1);
+    square!(e2, 1);
+    square!(e3, 1);
+    square!(e4, 1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Bivector, Mv<e12, e13, e14, e23, e24, e34>);
+
+    #[reify(Bivector as B)]
+    impl Split for B {
+        type Output = (impl Bivector, impl Bivector);
+        fn split(self) -> Self::Output {
+            self.split()
+        }
+    }
+}
+
+fn assert_near(a: &[Field], b: &[Field]) {
+    let close = a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-12);
+    assert!(close, "{a:?} != {b:?}");
+}
+
+#[test]
+fn test_split_screw() {
+    use pga3d::Mv_e23_e31_e12_e01_e02_e03 as Line;
+    let screw = Line {
+        e23: 0.5,
+        e31: 0.0,
+        e12: 1.0,
+        e01: -1.0,
+        e02: 0.0,
+        e03: 2.0,
+    };
+    assert!(screw.pinch().abs() > 1.0);
+    let (axis, ideal) = screw.split();
+    assert_near(&[axis.pinch()], &[0.0]);
+    assert_near(
+        &[
+            axis.e01 + ideal.e01,
+            axis.e02 + ideal.e02,
+            axis.e03 + ideal.e03,
+        ],
+        &[screw.e01, screw.e02, screw.e03],
+    );
+    assert_near(
+        &[axis.e23, axis.e31, axis.e12],
+        &[screw.e23, screw.e31, screw.e12],
+    );
+}
+
+#[test]
+fn test_split_spacetime() {
+    use sta::Mv_e01_e02_e03_e23_e31_e12 as Bivector;
+    let b = Bivector {
+        e01: 0.7,
+        e02: 0.2,
+        e03: 0.0,
+        e23: 1.3,
+        e31: 0.0,
+        e12: -0.4,
+    };
+    let (boost, rotation) = b.split();
+    assert_near(&[boost.pinch(), rotation.pinch()], &[0.0, 0.0]);
+    let sum = [
+        boost.e01 + rotation.e01,
+        boost.e02 + rotation.e02,
+        boost.e03 + rotation.e03,
+        boost.e23 + rotation.e23,
+        boost.e31 + rotation.e31,
+        boost.e12 + rotation.e12,
+    ];
+    assert_near(&sum, &[b.e01, b.e02, b.e03, b.e23, b.e31, b.e12]);
+}
+
+#[test]
+fn test_split_translation() {
+    use pga3d::Mv_e23_e31_e12_e01_e02_e03 as Line;
+    // a pure translation is already simple, and `<B * B>` is zero
+    let line = Line {
+        e23: 0.0,
+        e31: 0.0,
+        e12: 0.0,
+        e01: 1.0,
+        e02: 0.0,
+        e03: 0.0,
+    };
+    let (axis, ideal) = line.split();
+    assert_eq!(axis, line);
+    assert_near(&[ideal.e01, ideal.e02, ideal.e03], &[0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_split_null() {
+    use sta::Mv_e01_e02_e03_e23_e31_e12 as Bivector;
+    // a null bivector squares to zero, so both parts square to zero too
+    let b = Bivector {
+        e01: 1.0,
+        e02: 0.0,
+        e03: 0.0,
+        e23: 0.0,
+        e31: 0.0,
+        e12: 1.0,
+    };
+    let (simple, rest) = b.split();
+    assert_eq!(simple, b);
+    let rest = [rest.e01, rest.e02, rest.e03, rest.e23, rest.e31, rest.e12];
+    assert_near(&rest, &[0.0; 6]);
+}
+
+#[test]
+fn test_split_isoclinic() {
+    use vga4d::Mv_e12_e13_e14_e23_e24_e34 as Bivector;
+    // both rotations have the same angle, so there is no preferred pair of planes
+    let b = Bivector {
+        e12: 1.0,
+        e13: 0.0,
+        e14: 0.0,
+        e23: 0.0,
+        e24: 0.0,
+        e34: 1.0,
+    };
+    let (first, second) = b.split();
+    assert_eq!(first, b);
+    let second = [
+        second.e12, second.e13, second.e14, second.e23, second.e24, second.e34,
+    ];
+    assert_near(&second, &[0.0; 6]);
+    // while a generic one still splits into its two planes
+    let b = Bivector { e12: 2.0, ..b };
+    let (first, second) = b.split();
+    assert_near(&[first.e12, first.e34], &[0.0, 1.0]);
+    assert_near(&[second.e12, second.e34], &[2.0, 0.0]);
+}
@@ -0,0 +1,162 @@
+type Field = f64;
+
+pub trait Pow {
+    fn pow(self, n: Self) -> Self;
+}
+impl Pow for Field {
+    fn pow(self, n: Self) -> Self {
+        self.powf(n)
+    }
+}
+
+pub trait Factors {
+    type Output;
+    fn factors(self) -> Self::Output;
+}
+
+#[reefer::algebraic]
+#[allow(dead_code)]
+mod vga3d {
+    use super::*;
+
+    square!(e1, 1);
+    square!(e2, 1);
+    square!(e3, 1);
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Vector, Mv<e1, e2, e3>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Bivector, Mv<e23, e31, e12>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Blade, Mv<e1, e2, e3, e23, e31, e12>);
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    shape!(Trivector, Mv<e123>);
+
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    /// the plane of two vectors, or their line when they're parallel
+    pub fn span(a: A, b: B) -> impl Blade {
+        a.join(b)
+    }
+
+    #[reify(Bivector as A)]
+    #[reify(Bivector as B)]
+    /// the line two planes share, or the plane when they're the same
+    pub fn common(a: A, b: B) -> impl Blade {
+        a.meet(b)
+    }
+
+    #[reify(Bivector as B)]
+    impl Factors for B {
+        type Output = [impl Vector; 2];
+        /// two vectors spanning the plane
+        fn factors(self) -> Self::Output {
+            self.factorize()
+        }
+    }
+
+    #[reify(Trivector as T)]
+    pub fn frame(t: T) -> [impl Vector; 3] {
+        t.factorize()
+    }
+
+    #[reify(Vector as A)]
+    #[reify(Vector as B)]
+    pub fn wedge(a: A, b: B) -> impl Bivector {
+        a ^ b
+    }
+
+    #[reify(Vector as A)]
+    #[reify(Bivector as B)]
+    /// the vector when it lies in the plane, otherwise they only meet in a scalar
+    pub fn lies_in(a: A, b: B) -> impl Vector {
+        a.meet(b).grade(1)
+    }
+}
+
+use vga3d::{Mv_e1_e2_e3 as Vector, Mv_e23_e31_e12 as Bivector, Mv_e123 as Trivector};
+
+fn vector(e1: Field, e2: Field, e3: Field) -> Vector {
+    Vector { e1, e2, e3 }
+}
+
+fn bivector(e23: Field, e31: Field, e12: Field) -> Bivector {
+    Bivector { e23, e31, e12 }
+}
+
+fn assert_near(a: &[Field], b: &[Field]) {
+    let close = a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-12);
+    assert!(close, "{a:?} != {b:?}");
+}
+
+#[test]
+fn test_join() {
+    // disjoint vectors join into their wedge
+    let plane = vga3d::span(vector(1.0, 0.0, 0.0), vector(1.0, 2.0, 0.0));
+    assert_near(
+        &[
+            plane.e1, plane.e2, plane.e3, plane.e23, plane.e31, plane.e12,
+        ],
+        &[0.0, 0.0, 0.0, 0.0, 0.0, 2.0],
+    );
+    // parallel vectors only span a line, which `^` would lose
+    let line = vga3d::span(vector(1.0, 2.0, 3.0), vector(-2.0, -4.0, -6.0));
+    assert_near(
+        &[line.e1, line.e2, line.e3, line.e23, line.e31, line.e12],
+        &[1.0, 2.0, 3.0, 0.0, 0.0, 0.0],
+    );
+}
+
+#[test]
+fn test_meet() {
+    // the xy and yz planes share the y axis
+    let line = vga3d::common(bivector(0.0, 0.0, 1.0), bivector(1.0, 0.0, 0.0));
+    assert_near(&[line.e1, line.e2, line.e3], &[0.0, 1.0, 0.0]);
+    assert_near(&[line.e23, line.e31, line.e12], &[0.0, 0.0, 0.0]);
+    // the same plane twice is all they share
+    let plane = vga3d::common(bivector(1.0, 2.0, 0.5), bivector(2.0, 4.0, 1.0));
+    assert_near(&[plane.e1, plane.e2, plane.e3], &[0.0, 0.0, 0.0]);
+    let ratio = [plane.e31 / plane.e23, plane.e12 / plane.e23];
+    assert_near(&ratio, &[2.0, 0.5]);
+}
+
+#[test]
+fn test_meet_contained() {
+    // a vector in the plane is the meet itself
+    let v = vga3d::lies_in(vector(1.0, 1.0, 0.0), bivector(0.0, 0.0, 3.0));
+    let v = [v.e1, v.e2, v.e3];
+    assert_near(&[v[1] / v[0], v[2]], &[1.0, 0.0]);
+    // and one outside only meets it in the origin
+    let v = vga3d::lies_in(vector(1.0, 1.0, 1.0), bivector(0.0, 0.0, 3.0));
+    assert_near(&[v.e1, v.e2, v.e3], &[0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_factorize() {
+    for b in [
+        bivector(0.0, 0.0, 2.0),
+        bivector(1.0, -2.0, 0.5),
+        bivector(-3.0, 0.0, 0.0),
+    ] {
+        let [x, y] = b.factors();
+        let wedge = vga3d::wedge(x, y);
+        assert_near(&[wedge.e23, wedge.e31, wedge.e12], &[b.e23, b.e31, b.e12]);
+    }
+    // the largest coordinate picks the axes to project
+    let [x, y] = bivector(0.0, 0.0, 2.0).factors();
+    assert_near(
+        &[x.e1, x.e2, x.e3, y.e1, y.e2, y.e3],
+        &[1.0, 0.0, 0.0, 0.0, 2.0, 0.0],
+    );
+    // a zero blade has zero factors
+    let [x, _] = bivector(0.0, 0.0, 0.0).factors();
+    assert_near(&[x.e1, x.e2, x.e3], &[0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_factorize_volume() {
+    let [x, y, z] = vga3d::frame(Trivector { e123: -2.0 });
+    let plane = vga3d::wedge(x, y);
+    let volume = plane.e23 * z.e1 + plane.e31 * z.e2 + plane.e12 * z.e3;
+    assert_near(&[volume], &[-2.0]);
+}